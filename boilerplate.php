@@ -3,7 +3,89 @@
 /** Gets the pdo instance */
 function getPDO(): PDO
 {
-    static $pdo = new PDO("mysql:host=localhost;dbname=de-blauwe-loper", "root", "");
+    static $pdo = new PDO({{connection}});
 
     return $pdo;
 }
+
+/** Decodes a PostgreSQL array literal, like `{1,"a b",NULL}` */
+function decodeArray(string $value): array
+{
+    $values = array();
+    $length = strlen($value) - 1;
+    $current = "";
+    $quoted = false;
+    $wasQuoted = false;
+
+    for ($i = 1; $i < $length; $i++) {
+        $char = $value[$i];
+
+        if ($quoted) {
+            if ($char === "\\")
+                $current .= $value[++$i];
+            else if ($char === "\"")
+                $quoted = false;
+            else
+                $current .= $char;
+        } else if ($char === "\"") {
+            $quoted = $wasQuoted = true;
+        } else if ($char === ",") {
+            $values[] = !$wasQuoted && $current === "NULL" ? null : $current;
+            $current = "";
+            $wasQuoted = false;
+        } else {
+            $current .= $char;
+        }
+    }
+
+    if ($length > 1)
+        $values[] = !$wasQuoted && $current === "NULL" ? null : $current;
+
+    return $values;
+}
+
+/** Encodes an array as a PostgreSQL array literal */
+function encodeArray(array $values): string
+{
+    $values = array_map(function ($value) {
+        if ($value === null)
+            return "NULL";
+        if (is_bool($value))
+            return $value ? "t" : "f";
+        if ($value instanceof BackedEnum)
+            $value = $value->value;
+        if ($value instanceof DateTimeInterface)
+            $value = $value->format("Y-m-d H:i:s.uP");
+
+        return "\"" . addcslashes((string)$value, "\"\\") . "\"";
+    }, $values);
+
+    return "{" . implode(",", $values) . "}";
+}
+
+/** Reads a binary column, PostgreSQL returns `bytea` as a stream */
+function decodeBinary(mixed $value): string
+{
+    return is_resource($value) ? stream_get_contents($value) : $value;
+}
+
+/** Formats 16 bytes as a UUID, like MariaDB's `BIN_TO_UUID()` */
+function decodeUuid(string $value): string
+{
+    return vsprintf("%s%s-%s-%s-%s-%s%s%s", str_split(bin2hex($value), 4));
+}
+
+/** Converts a UUID to 16 bytes, like MariaDB's `UUID_TO_BIN()` */
+function encodeUuid(string $uuid): string
+{
+    return hex2bin(str_replace("-", "", $uuid));
+}
+
+/** Thrown when the database holds a value that isn't a case of its enum */
+class UnknownEnumValueException extends DomainException
+{
+    public function __construct(string $enum, string $value)
+    {
+        parent::__construct(sprintf("`%s` isn't a case of `%s`", $value, $enum));
+    }
+}
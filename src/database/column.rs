@@ -1,5 +1,3 @@
-use sql_parse::Type;
-
 #[derive(Debug)]
 pub(crate) struct Column {
     pub(crate) name: String,
@@ -7,6 +5,21 @@ pub(crate) struct Column {
     pub(crate) nullable: bool,
     pub(crate) auto_increment: bool,
     pub(crate) comment: String,
+    pub(crate) default: Option<ColumnDefault>,
+    /// Computed by the database from an expression, it can't be written.
+    pub(crate) generated: bool,
+    /// The path of a JSON column a generated column extracts.
+    pub(crate) json_path: Option<JsonPath>,
+    /// The PHP enum of enum and set columns, named for the whole schema.
+    pub(crate) enum_name: Option<String>,
+    /// The PHP class of a JSON column, from `--json-class`.
+    pub(crate) json_class: Option<String>,
+    /// Whether a `BINARY(16)` column holds a UUID, from `--uuid`.
+    pub(crate) uuid: bool,
+    /// The PHP property, named for the whole table.
+    pub(crate) property_name: Option<String>,
+    /// The PHP with-er, named for the whole table.
+    pub(crate) method_name: Option<String>,
 }
 
 impl Column {
@@ -16,6 +29,7 @@ impl Column {
         nullable: bool,
         auto_increment: bool,
         comment: String,
+        default: Option<ColumnDefault>,
     ) -> Self {
         Self {
             name,
@@ -23,45 +37,55 @@ impl Column {
             nullable,
             comment,
             auto_increment,
+            default,
+            generated: false,
+            json_path: None,
+            enum_name: None,
+            json_class: None,
+            uuid: false,
+            property_name: None,
+            method_name: None,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub(crate) enum ColumnType {
     Boolean,
     Int,
     Float,
+    /// An exact number with a precision and scale, unbounded when they aren't known.
+    Decimal(Option<u64>, Option<u64>),
     String,
-    Enum(Vec<String>),
+    Date,
+    Time,
+    DateTime,
+    /// A point in time, shown in the time zone of the connection.
+    Timestamp,
+    /// Bytes, with the length of fixed-size `BINARY(n)` columns.
+    Binary(Option<u64>),
+    /// Decoded JSON, like MariaDB's `JSON` or `LONGTEXT CHECK (json_valid(...))`.
+    Json,
+    /// The values, and the name of named types like PostgreSQL's `CREATE TYPE`.
+    Enum(Vec<String>, Option<String>),
+    /// Any number of the values, stored comma separated.
+    Set(Vec<String>),
+    Array(Box<ColumnType>),
 }
 
-impl From<Type<'_>> for ColumnType {
-    fn from(value: Type) -> Self {
-        match value {
-            sql_parse::Type::Boolean => ColumnType::Boolean,
-            sql_parse::Type::TinyInt(_)
-            | sql_parse::Type::SmallInt(_)
-            | sql_parse::Type::Integer(_)
-            | sql_parse::Type::Int(_)
-            | sql_parse::Type::BigInt(_) => ColumnType::Int,
-            sql_parse::Type::Char(_)
-            | sql_parse::Type::VarChar(_)
-            | sql_parse::Type::TinyText(_)
-            | sql_parse::Type::MediumText(_)
-            | sql_parse::Type::Text(_)
-            | sql_parse::Type::LongText(_) => ColumnType::String,
-            sql_parse::Type::Enum(value) => {
-                ColumnType::Enum(value.into_iter().map(|s| s.value.into()).collect())
-            }
-            sql_parse::Type::Float8 | sql_parse::Type::Float(_) | sql_parse::Type::Double(_) => {
-                ColumnType::Float
-            }
-            sql_parse::Type::DateTime(_)
-            | sql_parse::Type::Timestamp(_)
-            | sql_parse::Type::Time(_)
-            | sql_parse::Type::Date => ColumnType::String,
-            _ => unimplemented!(),
-        }
-    }
+#[derive(Debug)]
+pub(crate) enum ColumnDefault {
+    Null,
+    Boolean(bool),
+    Number(String),
+    String(String),
+    /// Evaluated by the database, like `CURRENT_TIMESTAMP` or `uuid()`.
+    Expression(String),
+}
+
+#[derive(Debug)]
+pub(crate) struct JsonPath {
+    pub(crate) column_name: String,
+    /// A MariaDB style path, like `$.address.city`.
+    pub(crate) path: String,
 }
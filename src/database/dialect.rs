@@ -0,0 +1,243 @@
+use std::{cmp::Reverse, str::FromStr};
+
+use sqlparser::{
+    dialect::GenericDialect,
+    tokenizer::{Token, Tokenizer},
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SQLDialect {
+    #[default]
+    MariaDB,
+    PostgreSQL,
+    Sqlite,
+}
+
+/// Keywords only one dialect has.
+const KEYWORD_MARKERS: &[(SQLDialect, &str)] = &[
+    (SQLDialect::MariaDB, "auto_increment"),
+    (SQLDialect::MariaDB, "unsigned"),
+    (SQLDialect::Sqlite, "autoincrement"),
+    (SQLDialect::Sqlite, "sqlite_sequence"),
+    (SQLDialect::PostgreSQL, "pg_catalog"),
+    (SQLDialect::PostgreSQL, "search_path"),
+];
+
+/// Data types only one dialect has, they only count after a column name.
+const TYPE_MARKERS: &[(SQLDialect, &str)] = &[
+    (SQLDialect::MariaDB, "tinyint"),
+    (SQLDialect::MariaDB, "mediumint"),
+    (SQLDialect::MariaDB, "tinytext"),
+    (SQLDialect::MariaDB, "mediumtext"),
+    (SQLDialect::MariaDB, "longtext"),
+    (SQLDialect::MariaDB, "tinyblob"),
+    (SQLDialect::MariaDB, "mediumblob"),
+    (SQLDialect::MariaDB, "longblob"),
+    (SQLDialect::PostgreSQL, "serial"),
+    (SQLDialect::PostgreSQL, "bigserial"),
+    (SQLDialect::PostgreSQL, "smallserial"),
+    (SQLDialect::PostgreSQL, "jsonb"),
+    (SQLDialect::PostgreSQL, "bytea"),
+    (SQLDialect::PostgreSQL, "timestamptz"),
+];
+
+/// Pairs of keywords only one dialect has.
+const PHRASE_MARKERS: &[(SQLDialect, &str)] = &[
+    (SQLDialect::MariaDB, "engine ="),
+    (SQLDialect::MariaDB, "default charset"),
+    (SQLDialect::Sqlite, "without rowid"),
+    (SQLDialect::PostgreSQL, "as identity"),
+    (SQLDialect::PostgreSQL, "create type"),
+    (SQLDialect::PostgreSQL, "owner to"),
+];
+
+impl SQLDialect {
+    /// Guesses the dialect of a schema dump from its keywords and data types,
+    /// `None` when no dialect has more markers than the others. Identifiers,
+    /// comments and strings don't count.
+    pub(crate) fn detect(source: &str) -> Option<Self> {
+        let tokens = Tokenizer::new(&GenericDialect {}, source).tokenize().ok()?;
+        let tokens = tokens
+            .into_iter()
+            .filter(|token| !matches!(token, Token::Whitespace(_)))
+            .collect::<Vec<_>>();
+
+        let word = |index: usize| match tokens.get(index) {
+            Some(Token::Word(word)) if word.quote_style.is_none() => word.value.to_lowercase(),
+            _ => String::new(),
+        };
+
+        let mut markers = Vec::new();
+        for (index, token) in tokens.iter().enumerate() {
+            let current = word(index);
+            let next = match tokens.get(index + 1) {
+                Some(next) => next.to_string().to_lowercase(),
+                None => String::new(),
+            };
+            // Data types follow the column name.
+            let is_type = index > 0 && matches!(tokens[index - 1], Token::Word(_));
+
+            let marker = match token {
+                Token::Word(word) if word.quote_style == Some('`') => {
+                    Some((SQLDialect::MariaDB, "`"))
+                }
+                Token::DoubleColon => Some((SQLDialect::PostgreSQL, "::")),
+                _ => KEYWORD_MARKERS
+                    .iter()
+                    .find(|(_, marker)| *marker == current)
+                    .or_else(|| {
+                        TYPE_MARKERS
+                            .iter()
+                            .find(|(_, marker)| is_type && *marker == current)
+                    })
+                    .or_else(|| {
+                        PHRASE_MARKERS
+                            .iter()
+                            .find(|(_, marker)| *marker == format!("{} {}", current, next))
+                    })
+                    .copied(),
+            };
+
+            if let Some(marker) = marker {
+                if !markers.contains(&marker) {
+                    markers.push(marker);
+                }
+            }
+        }
+
+        // The dialect with the most distinct markers wins.
+        let count = |dialect| {
+            markers
+                .iter()
+                .filter(|(other, _)| *other == dialect)
+                .count()
+        };
+        let mut counts = [
+            SQLDialect::MariaDB,
+            SQLDialect::PostgreSQL,
+            SQLDialect::Sqlite,
+        ]
+        .map(|dialect| (count(dialect), dialect));
+        counts.sort_by_key(|(count, _)| Reverse(*count));
+
+        match counts {
+            [(0, _), ..] => None,
+            [(first, _), (second, _), _] if first == second => None,
+            [(_, dialect), ..] => Some(dialect),
+        }
+    }
+
+    /// Quotes an identifier, schema qualified names are quoted per part.
+    pub(crate) fn quote(&self, identifier: &str) -> String {
+        let quote = match self {
+            SQLDialect::MariaDB => "`",
+            SQLDialect::PostgreSQL | SQLDialect::Sqlite => "\"",
+        };
+
+        identifier
+            .split('.')
+            // A quote inside an identifier is escaped by doubling it.
+            .map(|part| {
+                format!(
+                    "{}{}{}",
+                    quote,
+                    part.replace(quote, &quote.repeat(2)),
+                    quote
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// The comparison operator that treats two `NULL`s as equal.
+    pub(crate) fn null_safe_equal(&self) -> &'static str {
+        match self {
+            SQLDialect::MariaDB => "<=>",
+            SQLDialect::PostgreSQL => "IS NOT DISTINCT FROM",
+            SQLDialect::Sqlite => "IS",
+        }
+    }
+
+    /// Concatenates SQL expressions into a string.
+    pub(crate) fn concat(&self, expressions: &[String]) -> String {
+        match self {
+            SQLDialect::MariaDB => format!("CONCAT({})", expressions.join(", ")),
+            SQLDialect::PostgreSQL | SQLDialect::Sqlite => expressions.join(" || "),
+        }
+    }
+
+    /// The type of strings built by a query, MariaDB needs a length.
+    pub(crate) fn string_type(&self) -> &'static str {
+        match self {
+            SQLDialect::MariaDB => "CHAR(10000)",
+            SQLDialect::PostgreSQL | SQLDialect::Sqlite => "TEXT",
+        }
+    }
+}
+
+impl FromStr for SQLDialect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mariadb" | "mysql" => Ok(SQLDialect::MariaDB),
+            "postgresql" | "postgres" => Ok(SQLDialect::PostgreSQL),
+            "sqlite" => Ok(SQLDialect::Sqlite),
+            _ => Err(format!("Unknown dialect `{}`", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SQLDialect;
+
+    #[test]
+    fn detects_dialect_markers() {
+        let mariadb = "CREATE TABLE `users` (`id` INT UNSIGNED AUTO_INCREMENT) ENGINE=InnoDB;";
+        let postgres = "CREATE TABLE users (id SERIAL PRIMARY KEY, data JSONB);";
+        let sqlite = "CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT) WITHOUT ROWID;";
+
+        assert_eq!(SQLDialect::detect(mariadb), Some(SQLDialect::MariaDB));
+        assert_eq!(SQLDialect::detect(postgres), Some(SQLDialect::PostgreSQL));
+        assert_eq!(SQLDialect::detect(sqlite), Some(SQLDialect::Sqlite));
+    }
+
+    #[test]
+    fn ignores_markers_in_identifiers() {
+        let source = "CREATE TABLE products (
+            id INT NOT NULL AUTO_INCREMENT,
+            serial_number VARCHAR(20),
+            bytea_size INT,
+            PRIMARY KEY (id)
+        );";
+
+        assert_eq!(SQLDialect::detect(source), Some(SQLDialect::MariaDB));
+    }
+
+    #[test]
+    fn ignores_markers_in_comments_and_strings() {
+        let source = "-- the owner to contact, cast with ::text
+            CREATE TABLE notes (
+                id INT NOT NULL AUTO_INCREMENT,
+                note VARCHAR(20) DEFAULT 'serial :: bytea',
+                PRIMARY KEY (id)
+            ); /* jsonb */";
+
+        assert_eq!(SQLDialect::detect(source), Some(SQLDialect::MariaDB));
+    }
+
+    #[test]
+    fn ignores_column_named_like_a_type() {
+        let source = "CREATE TABLE devices (serial VARCHAR(20), id INT UNSIGNED);";
+
+        assert_eq!(SQLDialect::detect(source), Some(SQLDialect::MariaDB));
+    }
+
+    #[test]
+    fn inconclusive_without_markers() {
+        let source = "CREATE TABLE products (id INT NOT NULL, serial_number VARCHAR(20));";
+
+        assert_eq!(SQLDialect::detect(source), None);
+    }
+}
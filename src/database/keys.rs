@@ -6,6 +6,10 @@ pub(crate) struct Key {
     pub(crate) unique: bool,
     pub(crate) table_name: String,
     pub(crate) column_names: Vec<String>,
+    /// The PHP getter, named for the whole table.
+    pub(crate) method_name: Option<String>,
+    /// The PHP getter by the JSON path the key's generated column extracts.
+    pub(crate) json_path_method_name: Option<String>,
 }
 
 impl Key {
@@ -20,6 +24,8 @@ impl Key {
             unique,
             table_name,
             column_names,
+            method_name: None,
+            json_path_method_name: None,
         }
     }
 
@@ -33,7 +39,7 @@ impl Key {
             .map(|column_name| &table.columns[column_name])
     }
 
-    pub(crate) fn iter_column_names<'a>(&'a self) -> impl Iterator<Item = &'a String> {
+    pub(crate) fn iter_column_names(&self) -> impl Iterator<Item = &String> {
         self.column_names.iter()
     }
 }
@@ -45,6 +51,11 @@ pub(crate) struct ForeignKey {
     pub(crate) column_names: Vec<String>,
     pub(crate) foreign_table_name: String,
     pub(crate) foreign_column_names: Vec<String>,
+    /// The PHP getter, named for the whole table.
+    pub(crate) method_name: Option<String>,
+    /// The PHP getters of the ancestors and descendants, for foreign keys
+    /// referencing their own table.
+    pub(crate) tree_method_names: Vec<String>,
 }
 
 impl ForeignKey {
@@ -61,6 +72,8 @@ impl ForeignKey {
             column_names,
             foreign_table_name,
             foreign_column_names,
+            method_name: None,
+            tree_method_names: Vec::new(),
         }
     }
 
@@ -74,7 +87,7 @@ impl ForeignKey {
             .map(|column_name| &table.columns[column_name])
     }
 
-    pub(crate) fn iter_column_names<'a>(&'a self) -> impl Iterator<Item = &'a String> {
+    pub(crate) fn iter_column_names(&self) -> impl Iterator<Item = &String> {
         self.column_names.iter()
     }
 
@@ -91,7 +104,55 @@ impl ForeignKey {
             .map(|column_name| &table.columns[column_name])
     }
 
-    pub(crate) fn iter_foreign_column_names<'a>(&'a self) -> impl Iterator<Item = &'a String> {
+    pub(crate) fn iter_foreign_column_names(&self) -> impl Iterator<Item = &String> {
         self.foreign_column_names.iter()
     }
 }
+
+/// A foreign key of another table, seen from the table it references.
+#[derive(Debug)]
+pub(crate) struct Reference {
+    pub(crate) table_name: String,
+    pub(crate) foreign_key_name: String,
+    /// The PHP getter, named for the whole table.
+    pub(crate) method_name: Option<String>,
+}
+
+impl Reference {
+    pub(crate) fn new(table_name: String, foreign_key_name: String) -> Self {
+        Self {
+            table_name,
+            foreign_key_name,
+            method_name: None,
+        }
+    }
+}
+
+/// A table linking two tables through two foreign keys making up its primary
+/// key, seen from the table one of them references.
+#[derive(Debug)]
+pub(crate) struct Junction {
+    pub(crate) table_name: String,
+    /// The foreign key referencing this table.
+    pub(crate) foreign_key_name: String,
+    /// The foreign key referencing the other table.
+    pub(crate) other_foreign_key_name: String,
+    /// The PHP methods, named for the whole table: the getter, `attach`,
+    /// `detach`, `sync` and the static getter the other table uses.
+    pub(crate) method_names: Vec<String>,
+}
+
+impl Junction {
+    pub(crate) fn new(
+        table_name: String,
+        foreign_key_name: String,
+        other_foreign_key_name: String,
+    ) -> Self {
+        Self {
+            table_name,
+            foreign_key_name,
+            other_foreign_key_name,
+            method_names: Vec::new(),
+        }
+    }
+}
@@ -1,4 +1,6 @@
 #![allow(dead_code)]
 pub(crate) mod column;
+pub(crate) mod dialect;
 pub(crate) mod keys;
+pub(crate) mod schema;
 pub(crate) mod table;
@@ -0,0 +1,21 @@
+use std::collections::HashMap;
+
+use super::{dialect::SQLDialect, table::Table};
+
+#[derive(Debug)]
+pub(crate) struct Schema {
+    pub(crate) dialect: SQLDialect,
+    pub(crate) tables: HashMap<String, Table>,
+    /// Named enum types, like PostgreSQL's `CREATE TYPE ... AS ENUM`.
+    pub(crate) enums: HashMap<String, Vec<String>>,
+}
+
+impl Schema {
+    pub(crate) fn new(dialect: SQLDialect) -> Self {
+        Self {
+            dialect,
+            tables: HashMap::new(),
+            enums: HashMap::new(),
+        }
+    }
+}
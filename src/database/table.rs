@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use super::{
     column::Column,
-    keys::{ForeignKey, Key},
+    keys::{ForeignKey, Junction, Key, Reference},
 };
 
 #[derive(Debug)]
@@ -15,28 +15,39 @@ pub(crate) struct Table {
     pub(crate) primary_key: Option<Key>,
     pub(crate) foreign_key_names: Vec<String>,
     pub(crate) foreign_keys: HashMap<String, ForeignKey>,
+    /// The indexes MariaDB adds for foreign keys without one, named like the
+    /// foreign key. They are only kept to be dropped.
+    pub(crate) foreign_key_index_names: Vec<String>,
+    pub(crate) comment: String,
+    /// The foreign keys of other tables referencing this table.
+    pub(crate) references: Vec<Reference>,
+    /// The junction tables linking this table to others.
+    pub(crate) junctions: Vec<Junction>,
+    /// The PHP class, named for the whole schema.
+    pub(crate) class_name: Option<String>,
 }
 
 impl Table {
+    /// A table without keys, they are added by their constraints.
     pub(crate) fn new(
         name: String,
         column_names: Vec<String>,
         columns: HashMap<String, Column>,
-        key_names: Vec<String>,
-        keys: HashMap<String, Key>,
-        primary_key: Option<Key>,
-        foreign_key_names: Vec<String>,
-        foreign_keys: HashMap<String, ForeignKey>,
     ) -> Self {
         Self {
             name,
             column_names,
             columns,
-            key_names,
-            keys,
-            primary_key,
-            foreign_key_names,
-            foreign_keys,
+            key_names: Vec::new(),
+            keys: HashMap::new(),
+            primary_key: None,
+            foreign_key_names: Vec::new(),
+            foreign_keys: HashMap::new(),
+            foreign_key_index_names: Vec::new(),
+            comment: String::new(),
+            references: Vec::new(),
+            junctions: Vec::new(),
+            class_name: None,
         }
     }
 
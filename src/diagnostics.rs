@@ -0,0 +1,148 @@
+use std::fmt;
+
+use sqlparser::tokenizer::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Level {
+    Error,
+    Warning,
+}
+
+#[derive(Debug)]
+pub(crate) struct Diagnostic {
+    pub(crate) level: Level,
+    pub(crate) message: String,
+    pub(crate) file: usize,
+    pub(crate) span: Span,
+}
+
+#[derive(Debug)]
+struct SourceFile {
+    name: String,
+    lines: Vec<String>,
+}
+
+/// Collects parser errors and unsupported constructs for every parsed file.
+#[derive(Debug, Default)]
+pub(crate) struct Diagnostics {
+    files: Vec<SourceFile>,
+    list: Vec<Diagnostic>,
+    /// Report unsupported constructs as warnings and skip them instead of failing.
+    pub(crate) skip_unsupported: bool,
+}
+
+impl Diagnostics {
+    pub(crate) fn new(skip_unsupported: bool) -> Self {
+        Self {
+            skip_unsupported,
+            ..Default::default()
+        }
+    }
+
+    /// Registers the file that the following diagnostics refer to.
+    pub(crate) fn set_file(&mut self, name: String, source: &str) {
+        let lines = source.lines().map(|line| line.to_string()).collect();
+
+        self.files.push(SourceFile { name, lines });
+    }
+
+    pub(crate) fn error(&mut self, span: Span, message: impl Into<String>) {
+        self.push(Level::Error, span, message.into());
+    }
+
+    pub(crate) fn warning(&mut self, span: Span, message: impl Into<String>) {
+        self.push(Level::Warning, span, message.into());
+    }
+
+    /// Reports a column or statement that is left out of the schema, it is an
+    /// error unless `skip_unsupported` is set.
+    pub(crate) fn skip(&mut self, span: Span, message: impl Into<String>) {
+        if self.skip_unsupported {
+            self.warning(span, message);
+        } else {
+            self.error(span, message);
+        }
+    }
+
+    pub(crate) fn has_errors(&self) -> bool {
+        self.list
+            .iter()
+            .any(|diagnostic| diagnostic.level == Level::Error)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    fn push(&mut self, level: Level, span: Span, message: String) {
+        self.list.push(Diagnostic {
+            level,
+            message,
+            file: self.files.len().saturating_sub(1),
+            span,
+        });
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diagnostic in &self.list {
+            write_diagnostic(f, diagnostic, self.files.get(diagnostic.file))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_diagnostic(
+    f: &mut fmt::Formatter<'_>,
+    diagnostic: &Diagnostic,
+    file: Option<&SourceFile>,
+) -> fmt::Result {
+    match diagnostic.level {
+        Level::Error => write!(f, "error: ")?,
+        Level::Warning => write!(f, "warning: ")?,
+    }
+    writeln!(f, "{}", diagnostic.message)?;
+
+    let Some(file) = file else {
+        return writeln!(f);
+    };
+
+    let start = diagnostic.span.start;
+    if start.line == 0 {
+        return writeln!(f, "  --> {}\n", file.name);
+    }
+
+    let line_number = start.line.to_string();
+    let gutter = " ".repeat(line_number.len());
+
+    writeln!(
+        f,
+        "{}--> {}:{}:{}",
+        gutter, file.name, start.line, start.column
+    )?;
+
+    let Some(line) = file.lines.get(start.line as usize - 1) else {
+        return writeln!(f);
+    };
+
+    writeln!(f, "{} |", gutter)?;
+    writeln!(f, "{} | {}", line_number, line)?;
+
+    // Keep tabs so the marker lines up with the snippet.
+    let indent = line
+        .chars()
+        .take(start.column.saturating_sub(1) as usize)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect::<String>();
+
+    let end = diagnostic.span.end;
+    let width = if end.line == start.line && end.column > start.column {
+        (end.column - start.column) as usize
+    } else {
+        1
+    };
+
+    writeln!(f, "{} | {}{}\n", gutter, indent, "^".repeat(width))
+}
@@ -2,42 +2,123 @@ use std::{
     env,
     fs::{create_dir_all, File},
     io::Write,
+    path::Path,
     time::Instant,
 };
 
-use convert_case::{Case, Casing};
-
-use crate::parsers::parse_file;
-use crate::writer::table::write_table;
+use crate::diagnostics::Diagnostics;
+use crate::migrations::collect_files;
+use crate::options::Options;
+use crate::parsers::{parse_files, sqlite};
+use crate::writer::{
+    boilerplate::write_boilerplate,
+    column::configure_columns,
+    enums::{collect_enums, name_enums, write_enum},
+    junctions::collect_junctions,
+    naming::{name_members, write_table_file_name},
+    references::collect_references,
+    table::write_table,
+};
 
 mod database;
+mod diagnostics;
+mod migrations;
+mod options;
 mod parsers;
 mod writer;
 
 fn main() {
-    let mut args = env::args();
-    args.next();
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            println!("{}", message);
+            std::process::exit(1);
+        }
+    };
 
-    let Some(file_name) = args.next() else {
-        println!("Expected file");
-        std::process::exit(1);
+    let mut diagnostics = Diagnostics::new(options.skip_unsupported);
+
+    let files = match collect_files(&options.file_names, options.until.as_deref()) {
+        Ok(files) => files,
+        Err(message) => {
+            println!("{}", message);
+            std::process::exit(1);
+        }
+    };
+
+    let schema = match files.as_slice() {
+        [file] if sqlite::is_database(file) => sqlite::read_database(file, &mut diagnostics)
+            .map_err(|error| format!("Couldn't read {}: {}", file.display(), error)),
+        files => parse_files(files, options.dialect, &mut diagnostics),
     };
 
-    let tables = parse_file(file_name).unwrap();
+    let mut schema = match schema {
+        Ok(schema) => schema,
+        Err(message) => {
+            println!("{}", message);
+            std::process::exit(1);
+        }
+    };
+
+    if !diagnostics.is_empty() {
+        eprint!("{}", diagnostics);
+    }
+
+    if diagnostics.has_errors() {
+        eprintln!("Aborted, use --skip-unsupported to skip the offending columns and statements");
+        std::process::exit(1);
+    }
+
+    name_enums(&mut schema, &options);
+    configure_columns(&mut schema, &options);
+    collect_references(&mut schema);
+    collect_junctions(&mut schema);
+
+    let mut diagnostics = Diagnostics::new(false);
+    name_members(&mut schema, &options, &mut diagnostics);
+
+    if !diagnostics.is_empty() {
+        eprint!("{}", diagnostics);
+    }
+
+    if diagnostics.has_errors() {
+        eprintln!("Aborted, use --class-name, --property-name or --method-name to rename");
+        std::process::exit(1);
+    }
+
     create_dir_all("php").unwrap();
 
-    for (table_name, table) in &tables {
-        let file_name = format!("php/{}.php", table_name.to_case(Case::Camel));
+    // The boilerplate is adjusted to the database, so it is only written once.
+    let boilerplate = Path::new("php/boilerplate.php");
+    if options.overwrite_boilerplate || !boilerplate.exists() {
+        let mut file = File::create(boilerplate).unwrap();
+        file.write_all(write_boilerplate(schema.dialect, &options).as_bytes())
+            .unwrap();
+    }
+
+    for (table_name, table) in &schema.tables {
+        let file_name = format!("php/{}.php", write_table_file_name(table));
         let mut file = File::create(file_name).unwrap();
 
         let time = Instant::now();
 
         let mut buffer = write_file_start();
-        buffer += &write_table(table);
+        buffer += &write_table(table, &schema, &options);
 
         println!("Generated {} in {:?}", table_name, time.elapsed());
 
-        file.write(buffer.replace("\t", "    ").as_bytes()).unwrap();
+        file.write_all(buffer.replace("\t", "    ").as_bytes())
+            .unwrap();
+    }
+
+    for (name, values) in collect_enums(&schema) {
+        let mut file = File::create(format!("php/{}.php", name)).unwrap();
+
+        let mut buffer = write_file_start();
+        buffer += &write_enum(&name, &values);
+
+        file.write_all(buffer.replace("\t", "    ").as_bytes())
+            .unwrap();
     }
 }
 
@@ -0,0 +1,163 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Expands directories into the `.sql` files inside them and orders all files
+/// by their version prefix, or by name when they don't have one.
+pub(crate) fn collect_files(paths: &[String], until: Option<&str>) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        let path = Path::new(path);
+
+        if !path.is_dir() {
+            files.push(path.to_path_buf());
+            continue;
+        }
+
+        let entries = fs::read_dir(path)
+            .map_err(|error| format!("Couldn't read {}: {}", path.display(), error))?;
+
+        for entry in entries {
+            let entry =
+                entry.map_err(|error| format!("Couldn't read {}: {}", path.display(), error))?;
+            let path = entry.path();
+
+            if path.is_file() && path.extension().is_some_and(|extension| extension == "sql") {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort_by_cached_key(|path| {
+        let name = file_name(path);
+
+        (parse_version(&name), name)
+    });
+
+    let Some(until) = until else {
+        return Ok(files);
+    };
+
+    let Some(index) = files.iter().rposition(|path| is_migration(path, until)) else {
+        return Err(format!("Unknown migration `{}`", until));
+    };
+    files.truncate(index + 1);
+
+    Ok(files)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// The number a migration file starts with, like `42` for `0042_add_users.sql`.
+fn parse_version(name: &str) -> Option<u64> {
+    let digits = name
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+
+    digits.parse().ok()
+}
+
+/// Matches a migration by version (`42`, `0042`), file name or file stem.
+fn is_migration(path: &Path, migration: &str) -> bool {
+    let name = file_name(path);
+
+    match parse_version(migration) {
+        Some(version) if migration.chars().all(|c| c.is_ascii_digit()) => {
+            parse_version(&name) == Some(version)
+        }
+        _ => {
+            name == migration
+                || path
+                    .file_stem()
+                    .is_some_and(|stem| stem.to_string_lossy() == migration)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        path::{Path, PathBuf},
+        process,
+    };
+
+    use super::collect_files;
+
+    /// Creates a directory of empty migrations, named after the test.
+    fn create_migrations(test: &str, names: &[&str]) -> PathBuf {
+        let directory =
+            env::temp_dir().join(format!("database-classes-{}-{}", test, process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+
+        for name in names {
+            fs::write(directory.join(name), "").unwrap();
+        }
+
+        directory
+    }
+
+    fn collect_names(directory: &Path, until: Option<&str>) -> Result<Vec<String>, String> {
+        let files = collect_files(&[directory.display().to_string()], until)?;
+
+        Ok(files
+            .iter()
+            .map(|file| file.file_name().unwrap().to_string_lossy().into_owned())
+            .collect())
+    }
+
+    #[test]
+    fn orders_migrations_by_version() {
+        let directory = create_migrations(
+            "order",
+            &[
+                "10_add_orders.sql",
+                "2_add_users.sql",
+                "0001_init.sql",
+                "seed.sql",
+                "notes.txt",
+            ],
+        );
+
+        let names = collect_names(&directory, None);
+        fs::remove_dir_all(&directory).unwrap();
+
+        // Files without a version come first, other files than `.sql` are ignored.
+        assert_eq!(
+            names.unwrap(),
+            [
+                "seed.sql",
+                "0001_init.sql",
+                "2_add_users.sql",
+                "10_add_orders.sql"
+            ]
+        );
+    }
+
+    #[test]
+    fn stops_at_the_until_migration() {
+        let directory = create_migrations(
+            "until",
+            &["0001_init.sql", "0002_add_users.sql", "0003_add_orders.sql"],
+        );
+
+        let by_version = collect_names(&directory, Some("2"));
+        let by_stem = collect_names(&directory, Some("0002_add_users"));
+        let by_name = collect_names(&directory, Some("0001_init.sql"));
+        let unknown = collect_names(&directory, Some("0004"));
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(by_version.unwrap(), ["0001_init.sql", "0002_add_users.sql"]);
+        assert_eq!(by_stem.unwrap(), ["0001_init.sql", "0002_add_users.sql"]);
+        assert_eq!(by_name.unwrap(), ["0001_init.sql"]);
+        assert_eq!(unknown.unwrap_err(), "Unknown migration `0004`");
+    }
+}
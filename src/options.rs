@@ -0,0 +1,202 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use crate::database::dialect::SQLDialect;
+
+/// Options passed on the command line.
+#[derive(Debug, Default)]
+pub(crate) struct Options {
+    /// Schema files and directories of migrations, applied in order.
+    pub(crate) file_names: Vec<String>,
+    /// The dialect of the schema, detected from the file when not given.
+    pub(crate) dialect: Option<SQLDialect>,
+    /// Skip unsupported columns and statements instead of aborting.
+    pub(crate) skip_unsupported: bool,
+    /// The last migration to apply, by version or file name.
+    pub(crate) until: Option<String>,
+    /// The time zone of the application, applied to `TIMESTAMP` columns.
+    pub(crate) timezone: Option<String>,
+    /// Let PDO return native types instead of casting the strings it returns.
+    pub(crate) native_types: bool,
+    /// Rewrite `php/boilerplate.php`, which is otherwise only written when missing.
+    pub(crate) overwrite_boilerplate: bool,
+    /// Enums shared by several columns, by `table.column`.
+    pub(crate) shared_enums: HashMap<String, String>,
+    /// What hydration does with values an enum doesn't have.
+    pub(crate) unknown_enum_values: UnknownEnumValues,
+    /// The class of `DECIMAL` values, like `Brick\Math\BigDecimal`, strings when not given.
+    pub(crate) decimal_class: Option<String>,
+    /// Classes of JSON columns by `table.column`, created with `fromArray()` and
+    /// encoded through `JsonSerializable`. Other JSON columns are arrays.
+    pub(crate) json_classes: HashMap<String, String>,
+    /// `BINARY(16)` columns holding a UUID by `table.column`, exposed as RFC
+    /// 4122 strings.
+    pub(crate) uuid_columns: HashSet<String>,
+    /// Prefixes left out of class names, like `wp_`.
+    pub(crate) table_prefixes: Vec<String>,
+    /// Singulars of words the inflection gets wrong, by plural.
+    pub(crate) singulars: HashMap<String, String>,
+    /// Class names by table.
+    pub(crate) class_names: HashMap<String, String>,
+    /// Property names by `table.column`.
+    pub(crate) property_names: HashMap<String, String>,
+    /// Getter names by `table.key` for keys and foreign keys, and by
+    /// `table.other_table.foreign_key` for foreign keys of other tables. The
+    /// other methods by their default name after the junction table, key,
+    /// foreign key or column, like `users.user_roles.attachRole`.
+    pub(crate) method_names: HashMap<String, String>,
+    /// What naming does with names that collide or are reserved.
+    pub(crate) name_conflicts: NameConflicts,
+}
+
+impl Options {
+    pub(crate) fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--skip-unsupported" => options.skip_unsupported = true,
+                "--native-types" => options.native_types = true,
+                "--overwrite-boilerplate" => options.overwrite_boilerplate = true,
+                "--dialect" => options.dialect = Some(parse_value(&arg, args.next())?.parse()?),
+                "--until" => options.until = Some(parse_value(&arg, args.next())?),
+                "--timezone" => options.timezone = Some(parse_value(&arg, args.next())?),
+                "--decimal-class" => options.decimal_class = Some(parse_value(&arg, args.next())?),
+                "--uuid" => {
+                    let value = parse_value(&arg, args.next())?;
+                    if !value.contains('.') {
+                        return Err(format!("Expected `table.column` after `{}`", arg));
+                    }
+
+                    options.uuid_columns.insert(value);
+                }
+                "--shared-enum" => {
+                    let value = parse_value(&arg, args.next())?;
+                    let Some((name, column_names)) = value.split_once('=') else {
+                        return Err(format!("Expected `Enum=table.column,...` after `{}`", arg));
+                    };
+
+                    for column_name in column_names.split(',') {
+                        options.shared_enums.insert(column_name.into(), name.into());
+                    }
+                }
+                "--unknown-enum-values" => {
+                    options.unknown_enum_values = parse_value(&arg, args.next())?.parse()?
+                }
+                "--json-class" => {
+                    let value = parse_value(&arg, args.next())?;
+                    let Some((column_name, class)) = value
+                        .split_once('=')
+                        .filter(|(column_name, _)| column_name.contains('.'))
+                    else {
+                        return Err(format!("Expected `table.column=Class` after `{}`", arg));
+                    };
+
+                    options
+                        .json_classes
+                        .insert(column_name.into(), class.into());
+                }
+                "--strip-prefix" => options.table_prefixes.push(parse_value(&arg, args.next())?),
+                "--singular" => {
+                    let value = parse_value(&arg, args.next())?;
+                    let Some((plural, singular)) = value.split_once('=') else {
+                        return Err(format!("Expected `plural=singular` after `{}`", arg));
+                    };
+
+                    options
+                        .singulars
+                        .insert(plural.to_lowercase(), singular.to_lowercase());
+                }
+                "--class-name" => parse_name(&arg, args.next(), &mut options.class_names)?,
+                "--property-name" => parse_name(&arg, args.next(), &mut options.property_names)?,
+                "--method-name" => parse_name(&arg, args.next(), &mut options.method_names)?,
+                "--name-conflicts" => {
+                    options.name_conflicts = parse_value(&arg, args.next())?.parse()?
+                }
+                _ if arg.starts_with("--") => return Err(format!("Unknown option `{}`", arg)),
+                _ => options.file_names.push(arg),
+            }
+        }
+
+        if options.file_names.is_empty() {
+            return Err("Expected file".into());
+        }
+
+        Ok(options)
+    }
+}
+
+/// The strategy for enum values in the database that aren't a case.
+#[derive(Debug, Default)]
+pub(crate) enum UnknownEnumValues {
+    /// Throw the `UnknownEnumValueException` of the boilerplate.
+    #[default]
+    Exception,
+    /// Use `null`, which makes every enum property nullable.
+    Null,
+    /// Use the case with this value, enums without it throw.
+    Fallback(String),
+}
+
+impl FromStr for UnknownEnumValues {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some(("fallback", value)) => Ok(UnknownEnumValues::Fallback(value.into())),
+            _ if s == "exception" => Ok(UnknownEnumValues::Exception),
+            _ if s == "null" => Ok(UnknownEnumValues::Null),
+            _ => Err(format!(
+                "Unknown strategy `{}`, expected `exception`, `null` or `fallback=value`",
+                s
+            )),
+        }
+    }
+}
+
+/// The strategy for generated names that collide or are reserved in PHP.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) enum NameConflicts {
+    /// Add a suffix and warn.
+    #[default]
+    Suffix,
+    /// Abort, so the name can be chosen with `--class-name`, `--property-name`
+    /// or `--method-name`.
+    Error,
+}
+
+impl FromStr for NameConflicts {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "suffix" => Ok(NameConflicts::Suffix),
+            "error" => Ok(NameConflicts::Error),
+            _ => Err(format!(
+                "Unknown strategy `{}`, expected `suffix` or `error`",
+                s
+            )),
+        }
+    }
+}
+
+fn parse_name(
+    option: &str,
+    value: Option<String>,
+    names: &mut HashMap<String, String>,
+) -> Result<(), String> {
+    let value = parse_value(option, value)?;
+    let Some((key, name)) = value.split_once('=') else {
+        return Err(format!("Expected `name=NewName` after `{}`", option));
+    };
+
+    names.insert(key.into(), name.into());
+
+    Ok(())
+}
+
+fn parse_value(option: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or(format!("Expected a value after `{}`", option))
+}
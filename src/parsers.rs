@@ -1,240 +1,1836 @@
-use std::{collections::HashMap, fs, io, path::Path};
+use std::{collections::HashMap, fs, path::Path};
 
-use sql_parse::{
-    AlterSpecification, CreateDefinition, DataTypeProperty, Identifier, IndexCol, IndexType,
-    ParseOptions, SQLDialect, Statement, Type,
+use sqlparser::{
+    ast::{
+        AlterColumnOperation, AlterTableOperation, ArrayElemTypeDef, BinaryOperator, ColumnDef,
+        ColumnOption, ColumnOptionDef, CommentDef, CommentObject, DataType, EnumMember,
+        ExactNumberInfo, Expr, Function, FunctionArg, FunctionArgExpr, FunctionArguments, Ident,
+        MySQLColumnPosition, ObjectName, ObjectType, Spanned, Statement, TableConstraint,
+        TimezoneInfo, UnaryOperator, UserDefinedTypeRepresentation, Value,
+    },
+    dialect::{Dialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect},
+    keywords::Keyword,
+    parser::{Parser, ParserError},
+    tokenizer::{Location, Span, Token, TokenWithSpan, Tokenizer},
 };
 
-use crate::database::{
-    column::{Column, ColumnType},
-    keys::{ForeignKey, Key},
-    table::Table,
+use crate::{
+    database::{
+        column::{Column, ColumnDefault, ColumnType, JsonPath},
+        dialect::SQLDialect,
+        keys::{ForeignKey, Key},
+        schema::Schema,
+        table::Table,
+    },
+    diagnostics::Diagnostics,
 };
 
-pub(crate) fn parse_file<P: AsRef<Path>>(path: P) -> io::Result<HashMap<String, Table>> {
-    let contents = fs::read_to_string(path);
+pub(crate) mod sqlite;
 
-    contents.map(|s| {
-        let options = ParseOptions::new().dialect(SQLDialect::MariaDB);
-        let statements = sql_parse::parse_statements(s.as_str(), &mut Vec::new(), &options);
+/// Parses the schema files in order into one schema, the dialect is detected
+/// from the files when it isn't given.
+pub(crate) fn parse_files<P: AsRef<Path>>(
+    paths: &[P],
+    dialect: Option<SQLDialect>,
+    diagnostics: &mut Diagnostics,
+) -> Result<Schema, String> {
+    let mut files = Vec::new();
+    for path in paths {
+        let path = path.as_ref().display().to_string();
 
-        parse_statements(statements)
-    })
+        match fs::read_to_string(&path) {
+            Ok(contents) => files.push((path, contents)),
+            Err(error) => return Err(format!("Couldn't read {}: {}", path, error)),
+        }
+    }
+
+    let dialect = dialect.unwrap_or_else(|| {
+        let contents = files.iter().map(|(_, contents)| contents.as_str());
+
+        SQLDialect::detect(&contents.collect::<Vec<_>>().join("\n")).unwrap_or_else(|| {
+            let message = "couldn't detect the dialect, assuming MariaDB, use --dialect to set it";
+            diagnostics.warning(Span::empty(), message);
+
+            SQLDialect::MariaDB
+        })
+    });
+    let mut schema = Schema::new(dialect);
+
+    for (path, contents) in files {
+        diagnostics.set_file(path, &contents);
+
+        parse_statements(&contents, &mut schema, diagnostics);
+    }
+
+    Ok(schema)
+}
+
+fn parse_statements(source: &str, schema: &mut Schema, diagnostics: &mut Diagnostics) {
+    let dialect: Box<dyn Dialect> = match schema.dialect {
+        SQLDialect::MariaDB => Box::new(MySqlDialect {}),
+        SQLDialect::PostgreSQL => Box::new(PostgreSqlDialect {}),
+        SQLDialect::Sqlite => Box::new(SQLiteDialect {}),
+    };
+
+    let tokens = match Tokenizer::new(dialect.as_ref(), source).tokenize_with_location() {
+        Ok(tokens) => tokens,
+        Err(error) => {
+            let span = Span::new(error.location, error.location);
+            diagnostics.error(span, error.message);
+
+            return;
+        }
+    };
+
+    for tokens in split_statements(tokens) {
+        for statement in parse_statement_tokens(dialect.as_ref(), tokens, diagnostics) {
+            parse_statement(statement, schema, diagnostics);
+        }
+    }
+}
+
+/// Splits the tokens of a file on `;`, so a statement that fails to parse
+/// doesn't take the rest of the file with it.
+fn split_statements(tokens: Vec<TokenWithSpan>) -> Vec<Vec<TokenWithSpan>> {
+    let mut statements = Vec::new();
+    let mut statement = Vec::new();
+
+    for token in tokens {
+        match token.token {
+            Token::SemiColon | Token::EOF => statements.push(std::mem::take(&mut statement)),
+            _ => statement.push(token),
+        }
+    }
+    statements.push(statement);
+
+    statements
+        .into_iter()
+        .filter(|tokens| {
+            tokens
+                .iter()
+                .any(|token| !matches!(token.token, Token::Whitespace(_)))
+        })
+        .collect()
+}
+
+fn parse_statement_tokens(
+    dialect: &dyn Dialect,
+    tokens: Vec<TokenWithSpan>,
+    diagnostics: &mut Diagnostics,
+) -> Vec<Statement> {
+    let span = statement_span(&tokens);
+
+    match parse_tokens(dialect, tokens.clone()) {
+        Ok(statement) => vec![statement],
+        Err(_) if is_alter_table(&tokens) => parse_alter_table_tokens(dialect, tokens, diagnostics),
+        Err(error) => match rewrite_statement(&tokens) {
+            Some(statements) => statements
+                .into_iter()
+                .flat_map(|tokens| parse_statement_tokens(dialect, tokens, diagnostics))
+                .collect(),
+            None => {
+                report_parser_error(error, span, diagnostics);
+
+                Vec::new()
+            }
+        },
+    }
+}
+
+fn parse_tokens(
+    dialect: &dyn Dialect,
+    tokens: Vec<TokenWithSpan>,
+) -> Result<Statement, ParserError> {
+    let mut parser = Parser::new(dialect).with_tokens_with_locations(tokens);
+
+    let statement = parser.parse_statement()?;
+
+    let token = parser.peek_token();
+    if token.token != Token::EOF {
+        return parser.expected("end of statement", token);
+    }
+
+    Ok(statement)
+}
+
+fn is_alter_table(tokens: &[TokenWithSpan]) -> bool {
+    let tokens = significant_tokens(tokens);
+
+    matches!(
+        tokens.as_slice(),
+        [alter, table, ..] if keyword(alter) == Keyword::ALTER && keyword(table) == Keyword::TABLE
+    )
+}
+
+fn significant_tokens(tokens: &[TokenWithSpan]) -> Vec<TokenWithSpan> {
+    tokens
+        .iter()
+        .filter(|token| !matches!(token.token, Token::Whitespace(_)))
+        .cloned()
+        .collect()
+}
+
+fn keyword(token: &TokenWithSpan) -> Keyword {
+    match &token.token {
+        Token::Word(word) => word.keyword,
+        _ => Keyword::NoKeyword,
+    }
+}
+
+fn make_keyword(keyword: &str, span: Span) -> TokenWithSpan {
+    TokenWithSpan::new(Token::make_keyword(keyword), span)
+}
+
+/// Rewrites the MySQL statements the parser doesn't know into `ALTER TABLE`
+/// statements that mean the same.
+fn rewrite_statement(tokens: &[TokenWithSpan]) -> Option<Vec<Vec<TokenWithSpan>>> {
+    let tokens = significant_tokens(tokens);
+
+    match tokens.as_slice() {
+        // `RENAME TABLE a TO b, c TO d`
+        [rename, table, renames @ ..]
+            if keyword(rename) == Keyword::RENAME && keyword(table) == Keyword::TABLE =>
+        {
+            renames
+                .split(|token| token.token == Token::Comma)
+                .map(|rename_table| {
+                    let to = rename_table
+                        .iter()
+                        .position(|token| keyword(token) == Keyword::TO)?;
+                    let (old_name, new_name) = rename_table.split_at(to);
+
+                    Some(
+                        [
+                            &[make_keyword("ALTER", rename.span), table.clone()],
+                            old_name,
+                            &[make_keyword("RENAME", rename.span)],
+                            new_name,
+                        ]
+                        .concat(),
+                    )
+                })
+                .collect()
+        }
+        // `DROP INDEX name ON table`
+        [drop, index, name, on, table_name @ ..]
+            if keyword(drop) == Keyword::DROP
+                && keyword(index) == Keyword::INDEX
+                && keyword(on) == Keyword::ON =>
+        {
+            // Leave out options like `ALGORITHM = INPLACE` after the table name.
+            let mut end = 1;
+            while table_name
+                .get(end)
+                .is_some_and(|token| token.token == Token::Period)
+            {
+                end += 2;
+            }
+
+            Some(vec![[
+                &[
+                    make_keyword("ALTER", drop.span),
+                    make_keyword("TABLE", index.span),
+                ],
+                table_name.get(..end)?,
+                &[
+                    drop.clone(),
+                    make_keyword("CONSTRAINT", index.span),
+                    name.clone(),
+                ],
+            ]
+            .concat()])
+        }
+        _ => None,
+    }
+}
+
+/// Rewrites MySQL's `DROP INDEX`, `DROP KEY` and `DROP FOREIGN KEY` operations
+/// into `DROP CONSTRAINT`, which drops a key or foreign key by name.
+fn rewrite_drop_key(operation: &[TokenWithSpan]) -> Option<Vec<TokenWithSpan>> {
+    let tokens = significant_tokens(operation);
+
+    let (drop, index, name) = match tokens.as_slice() {
+        [drop, index, name] if matches!(keyword(index), Keyword::INDEX | Keyword::KEY) => {
+            (drop, index, name)
+        }
+        [drop, foreign, key, name]
+            if keyword(foreign) == Keyword::FOREIGN && keyword(key) == Keyword::KEY =>
+        {
+            (drop, foreign, name)
+        }
+        _ => return None,
+    };
+
+    if keyword(drop) != Keyword::DROP {
+        return None;
+    }
+
+    Some(vec![
+        drop.clone(),
+        make_keyword("CONSTRAINT", index.span),
+        name.clone(),
+    ])
+}
+
+/// Parses every operation of an `ALTER TABLE` on its own, so one unsupported
+/// operation (like phpMyAdmin's `AUTO_INCREMENT=5`) doesn't drop the others.
+fn parse_alter_table_tokens(
+    dialect: &dyn Dialect,
+    tokens: Vec<TokenWithSpan>,
+    diagnostics: &mut Diagnostics,
+) -> Vec<Statement> {
+    let mut parser = Parser::new(dialect).with_tokens_with_locations(tokens.clone());
+
+    let prefix = parser
+        .expect_keywords(&[Keyword::ALTER, Keyword::TABLE])
+        .and_then(|_| {
+            let _ = parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+            let _ = parser.parse_keyword(Keyword::ONLY);
+            parser.parse_object_name(false)
+        });
+
+    if let Err(error) = prefix {
+        report_parser_error(error, statement_span(&tokens), diagnostics);
+
+        return Vec::new();
+    }
+
+    let (prefix, operations) = tokens.split_at(parser.index());
+
+    let mut statements = Vec::new();
+
+    for operation in split_operations(operations) {
+        if is_table_option(&operation) {
+            continue;
+        }
+
+        let span = statement_span(&operation);
+        let operation = rewrite_drop_key(&operation).unwrap_or(operation);
+
+        match parse_tokens(dialect, [prefix, &operation].concat()) {
+            Ok(statement) => statements.push(statement),
+            Err(error) => report_parser_error(error, span, diagnostics),
+        }
+    }
+
+    statements
 }
 
-fn parse_statements(statements: Vec<Statement>) -> HashMap<String, Table> {
-    let mut tables = HashMap::new();
+fn split_operations(tokens: &[TokenWithSpan]) -> Vec<Vec<TokenWithSpan>> {
+    let mut operations = Vec::new();
+    let mut operation = Vec::new();
+    let mut depth = 0;
+
+    for token in tokens {
+        match token.token {
+            Token::LParen => depth += 1,
+            Token::RParen => depth -= 1,
+            Token::Comma if depth == 0 => {
+                operations.push(std::mem::take(&mut operation));
+                continue;
+            }
+            _ => (),
+        }
 
-    for statement in statements {
-        parse_statement(statement, &mut tables);
+        operation.push(token.clone());
     }
+    operations.push(operation);
+
+    operations
+}
+
+/// Table options don't change the generated classes, so they are ignored.
+fn is_table_option(tokens: &[TokenWithSpan]) -> bool {
+    let keyword = tokens.iter().find_map(|token| match &token.token {
+        Token::Word(word) => Some(word.keyword),
+        Token::Whitespace(_) => None,
+        _ => Some(Keyword::NoKeyword),
+    });
+
+    matches!(
+        keyword,
+        Some(
+            Keyword::AUTO_INCREMENT
+                | Keyword::ENGINE
+                | Keyword::DEFAULT
+                | Keyword::CHARSET
+                | Keyword::CHARACTER
+                | Keyword::COLLATE
+                | Keyword::COMMENT
+        )
+    )
+}
+
+fn statement_span(tokens: &[TokenWithSpan]) -> Span {
+    let mut tokens = tokens
+        .iter()
+        .filter(|token| !matches!(token.token, Token::Whitespace(_)));
+
+    let start = tokens
+        .next()
+        .map(|token| token.span)
+        .unwrap_or(Span::empty());
+    let end = tokens.next_back().map(|token| token.span).unwrap_or(start);
+
+    start.union(&end)
+}
+
+fn report_parser_error(error: ParserError, span: Span, diagnostics: &mut Diagnostics) {
+    let message = match error {
+        ParserError::TokenizerError(message) | ParserError::ParserError(message) => message,
+        ParserError::RecursionLimitExceeded => "recursion limit exceeded".into(),
+    };
+
+    // The parser appends the location to the message, use it to point at the
+    // offending token instead of the whole statement.
+    let location = message
+        .rsplit_once(" at Line: ")
+        .and_then(|(message, location)| {
+            let (line, column) = location.split_once(", Column: ")?;
+            let location = Location::new(line.parse().ok()?, column.parse().ok()?);
+
+            Some((message.to_string(), location))
+        });
 
-    tables
+    match location {
+        Some((message, location)) => diagnostics.skip(Span::new(location, location), message),
+        None => diagnostics.skip(span, message),
+    }
 }
 
-fn parse_statement(statement: Statement, tables: &mut HashMap<String, Table>) {
+fn parse_statement(statement: Statement, schema: &mut Schema, diagnostics: &mut Diagnostics) {
     match statement {
         Statement::CreateTable(value) => {
-            let name = value.identifier.value.to_string();
+            let name = parse_object_name(&value.name);
 
             let mut column_names = Vec::new();
             let mut columns = HashMap::new();
-            for create_definition in value.create_definitions {
-                let column = parse_create_definition(create_definition);
+            let mut declared_types = HashMap::new();
+            for column_def in &value.columns {
+                declared_types.insert(
+                    column_def.name.value.clone(),
+                    column_def.data_type.to_string(),
+                );
+
+                let Some(column) = parse_create_definition(column_def, schema, diagnostics) else {
+                    continue;
+                };
 
                 column_names.push(column.name.clone());
                 columns.insert(column.name.clone(), column);
             }
 
-            let table = Table::new(
-                name.clone(),
-                column_names,
+            let mut table = Table::new(name.clone(), column_names, columns);
+
+            if let Some(
+                CommentDef::WithEq(comment)
+                | CommentDef::WithoutEq(comment)
+                | CommentDef::AfterColumnDefsWithoutEq(comment),
+            ) = value.comment
+            {
+                table.comment = comment;
+            }
+
+            schema.tables.insert(name.clone(), table);
+
+            for column_def in value.columns {
+                parse_column_constraints(column_def, &name, schema, diagnostics);
+            }
+
+            for constraint in value.constraints {
+                parse_table_constraint(constraint, &name, schema, diagnostics);
+            }
+
+            if schema.dialect == SQLDialect::Sqlite {
+                parse_rowid_alias(schema.tables.get_mut(&name).unwrap(), &declared_types);
+            }
+        }
+        Statement::AlterTable {
+            name,
+            if_exists,
+            operations,
+            ..
+        } => {
+            let mut table_name = parse_object_name(&name);
+
+            if !schema.tables.contains_key(&table_name) {
+                if !if_exists {
+                    diagnostics.skip(name.span(), format!("unknown table `{}`", table_name));
+                }
+                return;
+            }
+
+            for operation in operations {
+                match operation {
+                    AlterTableOperation::RenameTable {
+                        table_name: new_name,
+                    } => {
+                        let span = new_name.span();
+                        let new_name = parse_new_table_name(&new_name, &table_name);
+
+                        if schema.tables.contains_key(&new_name) {
+                            diagnostics.skip(span, format!("table `{}` already exists", new_name));
+                            continue;
+                        }
+
+                        parse_rename_table(&table_name, &new_name, schema);
+                        table_name = new_name;
+                    }
+                    operation => {
+                        parse_alter_specification(operation, &table_name, schema, diagnostics)
+                    }
+                }
+            }
+        }
+        Statement::Drop {
+            object_type: ObjectType::Table,
+            if_exists,
+            names,
+            ..
+        } => {
+            for name in names {
+                let table_name = parse_object_name(&name);
+
+                if schema.tables.contains_key(&table_name) {
+                    parse_drop_table(&table_name, schema);
+                } else if !if_exists {
+                    diagnostics.skip(name.span(), format!("unknown table `{}`", table_name));
+                }
+            }
+        }
+        Statement::Drop {
+            object_type: ObjectType::Index,
+            if_exists,
+            names,
+            ..
+        } => {
+            for name in names {
+                let index_name = name
+                    .0
+                    .last()
+                    .map(|part| part.value.clone())
+                    .unwrap_or_default();
+
+                // Index names are unique in the whole schema, the table isn't given.
+                let table = schema
+                    .tables
+                    .values_mut()
+                    .find(|table| table.keys.contains_key(&index_name));
+
+                match table {
+                    Some(table) => retain_keys(table, |key| key.name != index_name),
+                    None if !if_exists => {
+                        diagnostics.skip(name.span(), format!("unknown index `{}`", index_name))
+                    }
+                    None => (),
+                }
+            }
+        }
+        Statement::CreateIndex(value) => {
+            let table_name = parse_object_name(&value.table_name);
+
+            if !schema.tables.contains_key(&table_name) {
+                let message = format!("unknown table `{}`", table_name);
+                diagnostics.skip(value.table_name.span(), message);
+                return;
+            }
+
+            let mut columns = Vec::new();
+            for column in value.columns {
+                let Expr::Identifier(ident) = column.expr else {
+                    let message = format!("unsupported index expression `{}`", column.expr);
+                    diagnostics.skip(column.expr.span(), message);
+                    return;
+                };
+
+                columns.push(ident);
+            }
+
+            let name = value
+                .name
+                .map(|name| parse_object_name(&name))
+                .unwrap_or(columns[0].value.clone());
+
+            parse_add_index(
+                name,
+                false,
+                value.unique,
                 columns,
-                Vec::new(),
-                HashMap::new(),
-                None,
-                Vec::new(),
-                HashMap::new(),
+                &table_name,
+                schema,
+                diagnostics,
             );
+        }
+        Statement::CreateType {
+            name,
+            representation: UserDefinedTypeRepresentation::Enum { labels },
+        } => {
+            let values = labels.into_iter().map(|label| label.value).collect();
+
+            schema.enums.insert(parse_object_name(&name), values);
+        }
+        Statement::Comment {
+            object_type: CommentObject::Column,
+            object_name,
+            comment,
+            ..
+        } => {
+            let span = object_name.span();
+
+            let mut parts = object_name.0;
+            let column_name = parts.pop().map(|part| part.value).unwrap_or_default();
+            let table_name = parse_object_name(&ObjectName(parts));
 
-            tables.insert(name, table);
+            let column = schema
+                .tables
+                .get_mut(&table_name)
+                .and_then(|table| table.columns.get_mut(&column_name));
+
+            match column {
+                Some(column) => column.comment = comment.unwrap_or_default(),
+                None => {
+                    let message = format!("unknown column `{}` in `{}`", column_name, table_name);
+                    diagnostics.skip(span, message);
+                }
+            }
         }
-        Statement::AlterTable(value) => {
-            let table_name = value.table.value.to_string();
+        Statement::Comment {
+            object_type: CommentObject::Table,
+            object_name,
+            comment,
+            ..
+        } => {
+            let span = object_name.span();
+            let table_name = parse_object_name(&object_name);
 
-            for alter_specification in value.alter_specifications {
-                parse_alter_specification(alter_specification, &table_name, tables)
+            match schema.tables.get_mut(&table_name) {
+                Some(table) => table.comment = comment.unwrap_or_default(),
+                None => diagnostics.skip(span, format!("unknown table `{}`", table_name)),
             }
         }
         _ => (),
     }
 }
 
-fn parse_create_definition(create_definition: CreateDefinition) -> Column {
-    match create_definition {
-        CreateDefinition::ColumnDefinition {
-            identifier,
-            data_type,
+/// Joins the parts of a name, the default PostgreSQL `public` schema is left out.
+fn parse_object_name(name: &ObjectName) -> String {
+    let mut parts = name.0.iter().map(|ident| ident.value.as_str()).peekable();
+
+    if name.0.len() > 1 && parts.peek() == Some(&"public") {
+        parts.next();
+    }
+
+    parts.collect::<Vec<_>>().join(".")
+}
+
+fn parse_create_definition(
+    column_def: &ColumnDef,
+    schema: &Schema,
+    diagnostics: &mut Diagnostics,
+) -> Option<Column> {
+    let name = column_def.name.value.clone();
+
+    let Some(type_) = parse_type(&column_def.data_type, schema) else {
+        diagnostics.skip(
+            column_def.span(),
+            format!("unsupported column type `{}`", column_def.data_type),
+        );
+        return None;
+    };
+
+    // Serial columns are `NOT NULL`.
+    let auto_increment = is_serial(&column_def.data_type);
+
+    let mut column = Column::new(
+        name,
+        type_,
+        !auto_increment,
+        auto_increment,
+        "".into(),
+        None,
+    );
+
+    for option in &column_def.options {
+        parse_column_option(&option.option, &mut column);
+    }
+
+    Some(column)
+}
+
+fn parse_column_option(option: &ColumnOption, column: &mut Column) {
+    match option {
+        ColumnOption::NotNull => column.nullable = false,
+        ColumnOption::Null => column.nullable = true,
+        ColumnOption::DialectSpecific(tokens) if is_auto_increment(tokens) => {
+            column.auto_increment = true
+        }
+        ColumnOption::Default(expr) if is_sequence(expr) => column.auto_increment = true,
+        ColumnOption::Default(expr) => column.default = Some(parse_default(expr)),
+        // `GENERATED ... AS IDENTITY` is `NOT NULL`, generated columns have an
+        // expression instead.
+        ColumnOption::Generated {
+            generation_expr: None,
+            ..
+        } => {
+            column.auto_increment = true;
+            column.nullable = false;
+        }
+        ColumnOption::Generated {
+            generation_expr: Some(expr),
+            ..
         } => {
-            let name = identifier.value.to_string();
+            column.generated = true;
+            column.json_path = parse_json_path(expr);
+        }
+        ColumnOption::Comment(value) => column.comment = value.clone(),
+        // MariaDB's `JSON` is a `LONGTEXT` with a `json_valid` check.
+        ColumnOption::Check(expr)
+            if matches!(column.type_, ColumnType::String)
+                && parse_json_check(expr).is_some_and(|name| name == column.name) =>
+        {
+            column.type_ = ColumnType::Json
+        }
+        _ => (),
+    }
+}
 
-            let type_ = parse_type(data_type.type_);
+/// The column checked by `json_valid(column)`.
+fn parse_json_check(expr: &Expr) -> Option<String> {
+    let Expr::Function(function) = expr else {
+        return None;
+    };
 
-            let mut nullable = true;
-            let mut auto_increment = false;
-            let mut comment = "".into();
+    match parse_function_arguments(function, "json_valid")?.as_slice() {
+        [argument] => parse_column_name(argument),
+        _ => None,
+    }
+}
 
-            for property in data_type.properties {
-                match property {
-                    DataTypeProperty::NotNull(_) => nullable = false,
-                    DataTypeProperty::AutoIncrement(_) => auto_increment = true,
-                    DataTypeProperty::Comment(value) => comment = value.value.to_string(),
-                    _ => (),
-                }
+/// Recognizes `json_value(column, path)`, `json_unquote(json_extract(column, path))`
+/// and the `->` and `->>` operators.
+fn parse_json_path(expr: &Expr) -> Option<JsonPath> {
+    let (column, path) = match expr {
+        Expr::Nested(expr) | Expr::Cast { expr, .. } => return parse_json_path(expr),
+        Expr::Function(function) => {
+            if let Some([argument]) = parse_function_arguments(function, "json_unquote").as_deref()
+            {
+                return parse_json_path(argument);
             }
 
-            Column::new(name, type_, nullable, auto_increment, comment)
+            let arguments = parse_function_arguments(function, "json_value")
+                .or_else(|| parse_function_arguments(function, "json_extract"))?;
+            match arguments.as_slice() {
+                [column, path] => (*column, *path),
+                _ => return None,
+            }
         }
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Arrow | BinaryOperator::LongArrow,
+            right,
+        } => (left.as_ref(), right.as_ref()),
+        _ => return None,
+    };
+
+    let Expr::Value(Value::SingleQuotedString(path)) = path else {
+        return None;
+    };
+
+    // PostgreSQL names the key without a path.
+    let path = match path.starts_with('$') {
+        true => path.clone(),
+        false => format!("$.{}", path),
+    };
+
+    Some(JsonPath {
+        column_name: parse_column_name(column)?,
+        path,
+    })
+}
+
+/// The plain arguments of a call to the function `name`.
+fn parse_function_arguments<'a>(function: &'a Function, name: &str) -> Option<Vec<&'a Expr>> {
+    if !function.name.to_string().eq_ignore_ascii_case(name) {
+        return None;
+    }
+
+    let FunctionArguments::List(list) = &function.args else {
+        return None;
+    };
+
+    list.args
+        .iter()
+        .map(|argument| match argument {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Some(expr),
+            _ => None,
+        })
+        .collect()
+}
+
+fn parse_column_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Identifier(ident) => Some(ident.value.clone()),
+        Expr::CompoundIdentifier(idents) => idents.last().map(|ident| ident.value.clone()),
+        _ => None,
+    }
+}
+
+/// Parses the SQL of a default, like the `dflt_value` SQLite keeps.
+pub(crate) fn parse_default_sql(dialect: &dyn Dialect, sql: &str) -> ColumnDefault {
+    let expr = Parser::new(dialect)
+        .try_with_sql(sql)
+        .and_then(|mut parser| parser.parse_expr());
+
+    match expr {
+        Ok(expr) => parse_default(&expr),
+        Err(_) => ColumnDefault::Expression(sql.to_string()),
     }
 }
 
-fn parse_type(type_: Type) -> ColumnType {
+/// Literals become PHP default values, everything else is left to the database.
+fn parse_default(expr: &Expr) -> ColumnDefault {
+    match expr {
+        Expr::Value(Value::Null) => ColumnDefault::Null,
+        Expr::Value(Value::Boolean(value)) => ColumnDefault::Boolean(*value),
+        Expr::Value(Value::Number(value, _)) => ColumnDefault::Number(value.clone()),
+        Expr::Value(
+            Value::SingleQuotedString(value)
+            | Value::DoubleQuotedString(value)
+            | Value::EscapedStringLiteral(value),
+        ) => ColumnDefault::String(value.clone()),
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr: operand,
+        } => match parse_default(operand) {
+            ColumnDefault::Number(value) => ColumnDefault::Number(format!("-{}", value)),
+            _ => ColumnDefault::Expression(expr.to_string()),
+        },
+        // `pg_dump` writes string defaults as `'value'::type`.
+        Expr::Cast { expr: operand, .. } | Expr::Nested(operand) => match parse_default(operand) {
+            ColumnDefault::Expression(_) => ColumnDefault::Expression(expr.to_string()),
+            default => default,
+        },
+        _ => ColumnDefault::Expression(expr.to_string()),
+    }
+}
+
+fn is_auto_increment(tokens: &[Token]) -> bool {
+    tokens.iter().any(|token| match token {
+        Token::Word(word) => matches!(
+            word.keyword,
+            Keyword::AUTO_INCREMENT | Keyword::AUTOINCREMENT
+        ),
+        _ => false,
+    })
+}
+
+/// PostgreSQL's `SERIAL` types are integers with a sequence as default.
+fn is_serial(type_: &DataType) -> bool {
     match type_ {
-        sql_parse::Type::Boolean => ColumnType::Boolean,
-        sql_parse::Type::TinyInt(_)
-        | sql_parse::Type::SmallInt(_)
-        | sql_parse::Type::Integer(_)
-        | sql_parse::Type::Int(_)
-        | sql_parse::Type::BigInt(_) => ColumnType::Int,
-        sql_parse::Type::Char(_)
-        | sql_parse::Type::VarChar(_)
-        | sql_parse::Type::TinyText(_)
-        | sql_parse::Type::MediumText(_)
-        | sql_parse::Type::Text(_)
-        | sql_parse::Type::LongText(_) => ColumnType::String,
-        sql_parse::Type::Enum(value) => {
-            ColumnType::Enum(value.into_iter().map(|s| s.value.into()).collect())
-        }
-        sql_parse::Type::Float8 | sql_parse::Type::Float(_) | sql_parse::Type::Double(_) => {
-            ColumnType::Float
-        }
-        sql_parse::Type::DateTime(_)
-        | sql_parse::Type::Timestamp(_)
-        | sql_parse::Type::Time(_)
-        | sql_parse::Type::Date => ColumnType::String,
-        _ => unimplemented!(),
+        DataType::Custom(name, _) => matches!(
+            parse_object_name(name).to_lowercase().as_str(),
+            "smallserial" | "serial2" | "serial" | "serial4" | "bigserial" | "serial8"
+        ),
+        _ => false,
     }
 }
 
-fn parse_alter_specification(
-    alter_specification: AlterSpecification,
+/// A `nextval('..._seq')` default, the way `pg_dump` writes `SERIAL` columns.
+fn is_sequence(expr: &Expr) -> bool {
+    match expr {
+        Expr::Function(function) => {
+            parse_object_name(&function.name).eq_ignore_ascii_case("nextval")
+        }
+        _ => false,
+    }
+}
+
+/// An `INTEGER PRIMARY KEY` column is an alias for SQLite's `rowid`, so it gets
+/// a value on insert just like an auto-increment column.
+pub(crate) fn parse_rowid_alias(table: &mut Table, declared_types: &HashMap<String, String>) {
+    let Some(primary_key) = &table.primary_key else {
+        return;
+    };
+
+    let [column_name] = primary_key.column_names.as_slice() else {
+        return;
+    };
+
+    let is_integer = declared_types
+        .get(column_name)
+        .is_some_and(|type_| type_.eq_ignore_ascii_case("integer"));
+
+    if let Some(column) = table.columns.get_mut(column_name).filter(|_| is_integer) {
+        column.auto_increment = true;
+    }
+}
+
+/// Maps a declared SQLite type using the type affinity rules, booleans and
+/// dates get their own type even though SQLite stores them as numbers.
+pub(crate) fn parse_sqlite_type(declared_type: &str) -> ColumnType {
+    let name = declared_type.to_uppercase();
+
+    if name.starts_with("BOOL") {
+        ColumnType::Boolean
+    } else if name.contains("INT") {
+        ColumnType::Int
+    } else if name.contains("BLOB") {
+        ColumnType::Binary(None)
+    } else if ["CHAR", "CLOB", "TEXT"]
+        .iter()
+        .any(|affinity| name.contains(affinity))
+        || name.is_empty()
+    {
+        ColumnType::String
+    } else if ["REAL", "FLOA", "DOUB"]
+        .iter()
+        .any(|affinity| name.contains(affinity))
+    {
+        ColumnType::Float
+    } else if name.starts_with("JSON") {
+        ColumnType::Json
+    } else if name.starts_with("DEC") || name.starts_with("NUMERIC") {
+        parse_sqlite_decimal(&name)
+    } else if name.contains("TIMESTAMP") {
+        ColumnType::Timestamp
+    } else if name.contains("DATETIME") {
+        ColumnType::DateTime
+    } else if name.contains("DATE") {
+        ColumnType::Date
+    } else if name.contains("TIME") {
+        ColumnType::Time
+    } else {
+        // Everything else has `NUMERIC` affinity.
+        ColumnType::Float
+    }
+}
+
+/// MariaDB defaults to `DECIMAL(10,0)`, PostgreSQL's `NUMERIC` is unbounded.
+/// Reads the precision and scale of a declared type like `DECIMAL(10, 2)`.
+fn parse_sqlite_decimal(name: &str) -> ColumnType {
+    let arguments = name
+        .split_once('(')
+        .and_then(|(_, arguments)| arguments.split_once(')'))
+        .map(|(arguments, _)| {
+            arguments
+                .split(',')
+                .map(|argument| argument.trim().parse::<u64>().ok())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    match arguments.as_slice() {
+        [Some(precision)] => ColumnType::Decimal(Some(*precision), Some(0)),
+        [Some(precision), Some(scale)] => ColumnType::Decimal(Some(*precision), Some(*scale)),
+        _ => ColumnType::Decimal(None, None),
+    }
+}
+
+fn parse_decimal(info: &ExactNumberInfo, dialect: SQLDialect) -> ColumnType {
+    match info {
+        ExactNumberInfo::None if dialect == SQLDialect::MariaDB => {
+            ColumnType::Decimal(Some(10), Some(0))
+        }
+        ExactNumberInfo::None => ColumnType::Decimal(None, None),
+        ExactNumberInfo::Precision(precision) => ColumnType::Decimal(Some(*precision), Some(0)),
+        ExactNumberInfo::PrecisionAndScale(precision, scale) => {
+            ColumnType::Decimal(Some(*precision), Some(*scale))
+        }
+    }
+}
+
+fn parse_type(type_: &DataType, schema: &Schema) -> Option<ColumnType> {
+    if schema.dialect == SQLDialect::Sqlite {
+        return Some(parse_sqlite_type(&type_.to_string()));
+    }
+
+    let type_ = match type_ {
+        DataType::Bool | DataType::Boolean => ColumnType::Boolean,
+        DataType::TinyInt(_)
+        | DataType::UnsignedTinyInt(_)
+        | DataType::SmallInt(_)
+        | DataType::UnsignedSmallInt(_)
+        | DataType::MediumInt(_)
+        | DataType::UnsignedMediumInt(_)
+        | DataType::Integer(_)
+        | DataType::UnsignedInteger(_)
+        | DataType::Int(_)
+        | DataType::UnsignedInt(_)
+        | DataType::BigInt(_)
+        | DataType::UnsignedBigInt(_)
+        | DataType::Int2(_)
+        | DataType::Int4(_)
+        | DataType::Int8(_) => ColumnType::Int,
+        DataType::Char(_)
+        | DataType::Character(_)
+        | DataType::Varchar(_)
+        | DataType::CharacterVarying(_)
+        | DataType::TinyText
+        | DataType::MediumText
+        | DataType::Text
+        | DataType::LongText
+        | DataType::Uuid => ColumnType::String,
+        DataType::Binary(length) => ColumnType::Binary(*length),
+        DataType::Varbinary(_)
+        | DataType::Blob(_)
+        | DataType::TinyBlob
+        | DataType::MediumBlob
+        | DataType::LongBlob
+        | DataType::Bytes(_)
+        | DataType::Bytea => ColumnType::Binary(None),
+        DataType::JSON | DataType::JSONB => ColumnType::Json,
+        DataType::Enum(members, _) => ColumnType::Enum(
+            members
+                .iter()
+                .map(|member| match member {
+                    EnumMember::Name(name) | EnumMember::NamedValue(name, _) => name.clone(),
+                })
+                .collect(),
+            None,
+        ),
+        DataType::Set(members) => ColumnType::Set(members.clone()),
+        DataType::Float4
+        | DataType::Float8
+        | DataType::Float(_)
+        | DataType::Real
+        | DataType::Double
+        | DataType::DoublePrecision => ColumnType::Float,
+        DataType::Decimal(info)
+        | DataType::Dec(info)
+        | DataType::Numeric(info)
+        | DataType::BigNumeric(info)
+        | DataType::BigDecimal(info) => parse_decimal(info, schema.dialect),
+        DataType::Date => ColumnType::Date,
+        DataType::Time(_, _) => ColumnType::Time,
+        DataType::Datetime(_) => ColumnType::DateTime,
+        // PostgreSQL's `TIMESTAMP` is without time zone unless stated otherwise.
+        DataType::Timestamp(_, TimezoneInfo::None | TimezoneInfo::WithoutTimeZone)
+            if schema.dialect == SQLDialect::PostgreSQL =>
+        {
+            ColumnType::DateTime
+        }
+        DataType::Timestamp(_, _) => ColumnType::Timestamp,
+        DataType::Interval => ColumnType::String,
+        DataType::Array(
+            ArrayElemTypeDef::SquareBracket(type_, _) | ArrayElemTypeDef::AngleBracket(type_),
+        ) => ColumnType::Array(Box::new(parse_type(type_, schema)?)),
+        DataType::Custom(_, _) if is_serial(type_) => ColumnType::Int,
+        DataType::Custom(name, _) => {
+            let name = parse_object_name(name);
+            ColumnType::Enum(schema.enums.get(&name)?.clone(), Some(name))
+        }
+        _ => return None,
+    };
+
+    Some(type_)
+}
+
+/// Parses the column level `PRIMARY KEY`, `UNIQUE` and `REFERENCES` markers.
+fn parse_column_constraints(
+    column_def: ColumnDef,
+    table_name: &String,
+    schema: &mut Schema,
+    diagnostics: &mut Diagnostics,
+) {
+    let column = column_def.name;
+
+    for option in column_def.options {
+        match option.option {
+            ColumnOption::Unique { is_primary, .. } => {
+                let name = if is_primary {
+                    "".into()
+                } else {
+                    option
+                        .name
+                        .map(|name| name.value)
+                        .unwrap_or(column.value.clone())
+                };
+
+                parse_add_index(
+                    name,
+                    is_primary,
+                    true,
+                    vec![column.clone()],
+                    table_name,
+                    schema,
+                    diagnostics,
+                );
+            }
+            ColumnOption::ForeignKey {
+                foreign_table,
+                referred_columns,
+                ..
+            } => parse_add_foreign_key(
+                option.name,
+                vec![column.clone()],
+                foreign_table,
+                referred_columns,
+                table_name,
+                schema,
+                diagnostics,
+            ),
+            _ => (),
+        }
+    }
+}
+
+fn parse_table_constraint(
+    constraint: TableConstraint,
     table_name: &String,
-    tables: &mut HashMap<String, Table>,
+    schema: &mut Schema,
+    diagnostics: &mut Diagnostics,
 ) {
-    match alter_specification {
-        AlterSpecification::AddIndex {
-            add_span: _,
-            index_type,
-            if_not_exists: _,
+    match constraint {
+        TableConstraint::PrimaryKey { columns, .. } => parse_add_index(
+            "".into(),
+            true,
+            true,
+            columns,
+            table_name,
+            schema,
+            diagnostics,
+        ),
+        TableConstraint::Unique {
             name,
-            constraint: _,
-            cols,
-            index_options: _,
-        } => parse_add_index(index_type, cols, name, table_name, tables),
-        AlterSpecification::Modify {
-            modify_span: _,
-            if_exists: _,
-            col,
-            definition,
+            index_name,
+            columns,
+            ..
         } => {
-            let column_name = col.value.to_string();
+            let name = index_name
+                .or(name)
+                .map(|name| name.value)
+                .unwrap_or(columns[0].value.clone());
 
-            let mut column = tables
-                .get_mut(table_name)
-                .unwrap()
-                .columns
-                .get_mut(&column_name)
-                .unwrap();
+            parse_add_index(name, false, true, columns, table_name, schema, diagnostics)
+        }
+        TableConstraint::Index { name, columns, .. } => {
+            let name = name
+                .map(|name| name.value)
+                .unwrap_or(columns[0].value.clone());
 
-            column.type_ = parse_type(definition.type_);
+            parse_add_index(name, false, false, columns, table_name, schema, diagnostics)
+        }
+        TableConstraint::ForeignKey {
+            name,
+            columns,
+            foreign_table,
+            referred_columns,
+            ..
+        } => parse_add_foreign_key(
+            name,
+            columns,
+            foreign_table,
+            referred_columns,
+            table_name,
+            schema,
+            diagnostics,
+        ),
+        TableConstraint::Check { expr, .. } => {
+            let column = parse_json_check(&expr).and_then(|column_name| {
+                let table = schema.tables.get_mut(table_name)?;
+                table.columns.get_mut(&column_name)
+            });
 
-            for property in definition.properties {
-                match property {
-                    DataTypeProperty::NotNull(_) => column.nullable = false,
-                    DataTypeProperty::AutoIncrement(_) => column.auto_increment = true,
-                    DataTypeProperty::Comment(value) => column.comment = value.value.to_string(),
-                    _ => (),
+            if let Some(column) = column {
+                if matches!(column.type_, ColumnType::String) {
+                    column.type_ = ColumnType::Json;
                 }
             }
         }
-        AlterSpecification::AddForeignKey {
-            add_span: _,
-            constraint,
-            foreign_key_span: _,
-            if_not_exists: _,
-            name: _,
-            cols,
-            references_span: _,
-            references_table,
-            references_cols,
-            ons: _,
+        _ => (),
+    }
+}
+
+fn parse_alter_specification(
+    operation: AlterTableOperation,
+    table_name: &String,
+    schema: &mut Schema,
+    diagnostics: &mut Diagnostics,
+) {
+    match operation {
+        AlterTableOperation::AddConstraint(constraint) => {
+            parse_table_constraint(constraint, table_name, schema, diagnostics)
+        }
+        AlterTableOperation::AddColumn {
+            if_not_exists,
+            column_def,
+            column_position,
+            ..
         } => {
-            let name = constraint.unwrap().1.unwrap().value.to_string();
+            if schema.tables[table_name]
+                .columns
+                .contains_key(&column_def.name.value)
+            {
+                if !if_not_exists {
+                    let message = format!(
+                        "duplicate column `{}` in `{}`",
+                        column_def.name.value, table_name
+                    );
+                    diagnostics.skip(column_def.name.span, message);
+                }
+                return;
+            }
 
-            let columns = cols.into_iter().map(|c| c.name.value.into()).collect();
+            let Some(column) = parse_create_definition(&column_def, schema, diagnostics) else {
+                return;
+            };
 
-            let foreign_table = references_table.value.to_string();
+            let table = schema.tables.get_mut(table_name).unwrap();
+            table.column_names.push(column.name.clone());
+            table.columns.insert(column.name.clone(), column);
 
-            let foreign_columns = references_cols
-                .into_iter()
-                .map(|c| c.value.into())
-                .collect();
+            if let Some(position) = column_position {
+                parse_column_position(position, &column_def.name.value, table, diagnostics);
+            }
 
-            let foreign_key = ForeignKey::new(
-                name,
-                table_name.clone(),
-                columns,
-                foreign_table,
-                foreign_columns,
+            parse_column_constraints(column_def, table_name, schema, diagnostics);
+        }
+        AlterTableOperation::DropColumn {
+            column_name,
+            if_exists,
+            ..
+        } => parse_drop_column(&column_name, if_exists, table_name, schema, diagnostics),
+        AlterTableOperation::ChangeColumn {
+            old_name,
+            new_name,
+            data_type,
+            options,
+            column_position,
+        } => parse_change_column(
+            &old_name,
+            new_column_def(new_name, data_type, options),
+            column_position,
+            table_name,
+            schema,
+            diagnostics,
+        ),
+        AlterTableOperation::ModifyColumn {
+            col_name,
+            data_type,
+            options,
+            column_position,
+        } => parse_change_column(
+            &col_name.clone(),
+            new_column_def(col_name, data_type, options),
+            column_position,
+            table_name,
+            schema,
+            diagnostics,
+        ),
+        AlterTableOperation::RenameColumn {
+            old_column_name,
+            new_column_name,
+        } => {
+            parse_rename_column(
+                &old_column_name,
+                &new_column_name,
+                table_name,
+                schema,
+                diagnostics,
             );
+        }
+        AlterTableOperation::DropConstraint {
+            if_exists, name, ..
+        } => parse_drop_constraint(&name, if_exists, table_name, schema, diagnostics),
+        AlterTableOperation::DropPrimaryKey => {
+            schema.tables.get_mut(table_name).unwrap().primary_key = None
+        }
+        AlterTableOperation::AlterColumn { column_name, op } => {
+            let type_ = match &op {
+                AlterColumnOperation::SetDataType { data_type, .. } => {
+                    let Some(type_) = parse_type(data_type, schema) else {
+                        let message = format!("unsupported column type `{}`", data_type);
+                        diagnostics.skip(column_name.span, message);
+                        return;
+                    };
+
+                    Some(type_)
+                }
+                _ => None,
+            };
+
+            let Some(column) = parse_column_mut(&column_name, table_name, schema, diagnostics)
+            else {
+                return;
+            };
 
-            let table = tables.get_mut(table_name).unwrap();
-            table.foreign_key_names.push(foreign_key.name.clone());
-            table
-                .foreign_keys
-                .insert(foreign_key.name.clone(), foreign_key);
+            match op {
+                AlterColumnOperation::SetNotNull => column.nullable = false,
+                AlterColumnOperation::DropNotNull => column.nullable = true,
+                AlterColumnOperation::SetDefault { value } if is_sequence(&value) => {
+                    column.auto_increment = true
+                }
+                AlterColumnOperation::SetDefault { value } => {
+                    column.default = Some(parse_default(&value))
+                }
+                AlterColumnOperation::DropDefault => column.default = None,
+                AlterColumnOperation::AddGenerated { .. } => {
+                    column.auto_increment = true;
+                    column.nullable = false;
+                }
+                _ => (),
+            }
+
+            if let Some(type_) = type_ {
+                column.type_ = type_;
+            }
+        }
+        // Ownership doesn't change the generated classes, `pg_dump` writes one for every table.
+        AlterTableOperation::OwnerTo { .. } => (),
+        operation => {
+            let message = format!("unsupported ALTER TABLE operation `{}`", operation);
+            diagnostics.skip(operation.span(), message);
         }
-        _ => unimplemented!(),
     }
 }
 
-fn parse_add_index(
-    index_type: IndexType,
-    cols: Vec<IndexCol>,
-    name: Option<Identifier>,
+/// Replaces a column definition for `CHANGE` and `MODIFY`, renaming it first
+/// when the name differs.
+fn parse_change_column(
+    column_name: &Ident,
+    column_def: ColumnDef,
+    column_position: Option<MySQLColumnPosition>,
+    table_name: &String,
+    schema: &mut Schema,
+    diagnostics: &mut Diagnostics,
+) {
+    let Some(mut new_column) = parse_create_definition(&column_def, schema, diagnostics) else {
+        return;
+    };
+
+    if !parse_rename_column(
+        column_name,
+        &column_def.name,
+        table_name,
+        schema,
+        diagnostics,
+    ) {
+        return;
+    }
+
+    let table = schema.tables.get_mut(table_name).unwrap();
+
+    if table
+        .primary_key
+        .as_ref()
+        .is_some_and(|key| key.column_names.contains(&new_column.name))
+    {
+        new_column.nullable = false;
+    }
+
+    // The new definition replaces the whole column.
+    table.columns.insert(new_column.name.clone(), new_column);
+
+    if let Some(position) = column_position {
+        parse_column_position(position, &column_def.name.value, table, diagnostics);
+    }
+}
+
+/// The definition `CHANGE` and `MODIFY` give a column, as if it were created.
+fn new_column_def(name: Ident, data_type: DataType, options: Vec<ColumnOption>) -> ColumnDef {
+    ColumnDef {
+        name,
+        data_type,
+        collation: None,
+        options: options
+            .into_iter()
+            .map(|option| ColumnOptionDef { name: None, option })
+            .collect(),
+    }
+}
+
+/// Moves a column to the `FIRST` or `AFTER` position.
+fn parse_column_position(
+    position: MySQLColumnPosition,
+    column_name: &String,
+    table: &mut Table,
+    diagnostics: &mut Diagnostics,
+) {
+    // The target is looked up without the moved column, like MariaDB does
+    // for `AFTER` the column itself.
+    let index = match position {
+        MySQLColumnPosition::First => 0,
+        MySQLColumnPosition::After(after) => {
+            let index = table
+                .column_names
+                .iter()
+                .filter(|name| *name != column_name)
+                .position(|name| name == &after.value);
+
+            let Some(index) = index else {
+                let message = format!("unknown column `{}` in `{}`", after.value, table.name);
+                diagnostics.skip(after.span, message);
+                return;
+            };
+
+            index + 1
+        }
+    };
+
+    table.column_names.retain(|name| name != column_name);
+    table.column_names.insert(index, column_name.clone());
+}
+
+/// Renames a column in its table, its keys and the foreign keys referencing
+/// it, returns `false` when the column can't be renamed.
+fn parse_rename_column(
+    column_name: &Ident,
+    new_name: &Ident,
+    table_name: &String,
+    schema: &mut Schema,
+    diagnostics: &mut Diagnostics,
+) -> bool {
+    let table = schema.tables.get_mut(table_name).unwrap();
+
+    if !table.columns.contains_key(&column_name.value) {
+        let message = format!("unknown column `{}` in `{}`", column_name.value, table_name);
+        diagnostics.skip(column_name.span, message);
+        return false;
+    }
+
+    if column_name.value == new_name.value {
+        return true;
+    }
+
+    if table.columns.contains_key(&new_name.value) {
+        let message = format!("duplicate column `{}` in `{}`", new_name.value, table_name);
+        diagnostics.skip(new_name.span, message);
+        return false;
+    }
+
+    let (old_name, new_name) = (&column_name.value, &new_name.value);
+
+    let mut column = table.columns.remove(old_name).unwrap();
+    column.name = new_name.clone();
+    table.columns.insert(new_name.clone(), column);
+
+    rename_in(&mut table.column_names, old_name, new_name);
+
+    for key in table.keys.values_mut().chain(table.primary_key.as_mut()) {
+        rename_in(&mut key.column_names, old_name, new_name);
+    }
+
+    for foreign_key in table.foreign_keys.values_mut() {
+        rename_in(&mut foreign_key.column_names, old_name, new_name);
+    }
+
+    for table in schema.tables.values_mut() {
+        for foreign_key in table.foreign_keys.values_mut() {
+            if &foreign_key.foreign_table_name == table_name {
+                rename_in(&mut foreign_key.foreign_column_names, old_name, new_name);
+            }
+        }
+    }
+
+    true
+}
+
+fn rename_in(names: &mut [String], old_name: &str, new_name: &str) {
+    for name in names.iter_mut().filter(|name| *name == old_name) {
+        *name = new_name.to_string();
+    }
+}
+
+fn parse_drop_column(
+    column_name: &Ident,
+    if_exists: bool,
     table_name: &String,
-    tables: &mut HashMap<String, Table>,
+    schema: &mut Schema,
+    diagnostics: &mut Diagnostics,
 ) {
-    let columns: Vec<String> = cols.into_iter().map(|c| c.name.value.into()).collect();
-    let name = name.map(|name| name.value.to_string()).unwrap_or("".into());
+    let table = schema.tables.get_mut(table_name).unwrap();
 
-    let unique = match index_type {
-        sql_parse::IndexType::Primary(_) | sql_parse::IndexType::Unique(_) => true,
-        sql_parse::IndexType::Index(_) => false,
-        _ => {
-            println!("Encountered unkown column type");
-            false
+    if table.columns.remove(&column_name.value).is_none() {
+        if !if_exists {
+            let message = format!("unknown column `{}` in `{}`", column_name.value, table_name);
+            diagnostics.skip(column_name.span, message);
         }
+        return;
+    }
+
+    let column_name = &column_name.value;
+
+    table.column_names.retain(|name| name != column_name);
+
+    // The column is removed from its keys, keys without columns are dropped.
+    for key in table.keys.values_mut().chain(table.primary_key.as_mut()) {
+        key.column_names.retain(|name| name != column_name);
+    }
+
+    retain_keys(table, |key| !key.column_names.is_empty());
+
+    if table
+        .primary_key
+        .as_ref()
+        .is_some_and(|key| key.column_names.is_empty())
+    {
+        table.primary_key = None;
+    }
+
+    retain_foreign_keys(table, |foreign_key| {
+        !foreign_key.column_names.contains(column_name)
+    });
+}
+
+/// Drops a foreign key or key by name, `DROP INDEX` and `DROP FOREIGN KEY` end
+/// up here too. MySQL names a foreign key and its index the same, so the
+/// foreign key goes first.
+fn parse_drop_constraint(
+    name: &Ident,
+    if_exists: bool,
+    table_name: &String,
+    schema: &mut Schema,
+    diagnostics: &mut Diagnostics,
+) {
+    let table = schema.tables.get_mut(table_name).unwrap();
+
+    // PostgreSQL names the primary key `<table>_pkey`.
+    let table_name = table_name.rsplit('.').next().unwrap_or_default();
+    let primary_key_name = format!("{}_pkey", table_name);
+
+    if table.foreign_keys.contains_key(&name.value) {
+        retain_foreign_keys(table, |foreign_key| foreign_key.name != name.value);
+    } else if table.keys.contains_key(&name.value) {
+        retain_keys(table, |key| key.name != name.value);
+    } else if table.foreign_key_index_names.contains(&name.value) {
+        table
+            .foreign_key_index_names
+            .retain(|index_name| index_name != &name.value);
+    } else if name.value == primary_key_name && table.primary_key.is_some() {
+        table.primary_key = None;
+    } else if !if_exists {
+        let message = format!("unknown constraint `{}` in `{}`", name.value, table.name);
+        diagnostics.skip(name.span, message);
+    }
+}
+
+fn retain_keys(table: &mut Table, f: impl Fn(&Key) -> bool) {
+    table.keys.retain(|_, key| f(key));
+    table
+        .key_names
+        .retain(|key_name| table.keys.contains_key(key_name));
+}
+
+fn retain_foreign_keys(table: &mut Table, f: impl Fn(&ForeignKey) -> bool) {
+    table.foreign_keys.retain(|_, foreign_key| f(foreign_key));
+    table
+        .foreign_key_names
+        .retain(|foreign_key_name| table.foreign_keys.contains_key(foreign_key_name));
+}
+
+/// `RENAME TO` keeps a table in its schema unless the new name has one.
+fn parse_new_table_name(name: &ObjectName, table_name: &str) -> String {
+    let new_name = parse_object_name(name);
+
+    match table_name.rsplit_once('.') {
+        Some((schema_name, _)) if !new_name.contains('.') => {
+            format!("{}.{}", schema_name, new_name)
+        }
+        _ => new_name,
+    }
+}
+
+/// Renames a table and points the foreign keys referencing it to the new name.
+fn parse_rename_table(table_name: &String, new_name: &str, schema: &mut Schema) {
+    let mut table = schema.tables.remove(table_name).unwrap();
+
+    table.name = new_name.to_string();
+
+    for key in table.keys.values_mut().chain(table.primary_key.as_mut()) {
+        key.table_name = new_name.to_string();
+    }
+
+    for foreign_key in table.foreign_keys.values_mut() {
+        foreign_key.table_name = new_name.to_string();
+    }
+
+    schema.tables.insert(new_name.to_string(), table);
+
+    for table in schema.tables.values_mut() {
+        for foreign_key in table.foreign_keys.values_mut() {
+            if &foreign_key.foreign_table_name == table_name {
+                foreign_key.foreign_table_name = new_name.to_string();
+            }
+        }
+    }
+}
+
+/// Drops a table together with the foreign keys referencing it, dumps turn off
+/// the foreign key checks that would otherwise prevent this.
+fn parse_drop_table(table_name: &String, schema: &mut Schema) {
+    schema.tables.remove(table_name);
+
+    for table in schema.tables.values_mut() {
+        retain_foreign_keys(table, |foreign_key| {
+            &foreign_key.foreign_table_name != table_name
+        });
+    }
+}
+
+fn parse_column_mut<'a>(
+    column_name: &Ident,
+    table_name: &String,
+    schema: &'a mut Schema,
+    diagnostics: &mut Diagnostics,
+) -> Option<&'a mut Column> {
+    let column = schema
+        .tables
+        .get_mut(table_name)
+        .unwrap()
+        .columns
+        .get_mut(&column_name.value);
+
+    if column.is_none() {
+        let message = format!("unknown column `{}` in `{}`", column_name.value, table_name);
+        diagnostics.skip(column_name.span, message);
+    }
+
+    column
+}
+
+/// Checks that every column exists in the table, reporting the ones that don't.
+fn parse_column_names(
+    columns: Vec<Ident>,
+    table: &Table,
+    diagnostics: &mut Diagnostics,
+) -> Option<Vec<String>> {
+    let mut column_names = Vec::new();
+
+    for column in columns {
+        if !table.columns.contains_key(&column.value) {
+            let message = format!("unknown column `{}` in `{}`", column.value, table.name);
+            diagnostics.skip(column.span, message);
+            return None;
+        }
+
+        column_names.push(column.value);
+    }
+
+    Some(column_names)
+}
+
+fn parse_add_index(
+    name: String,
+    primary: bool,
+    unique: bool,
+    columns: Vec<Ident>,
+    table_name: &String,
+    schema: &mut Schema,
+    diagnostics: &mut Diagnostics,
+) {
+    let table = schema.tables.get_mut(table_name).unwrap();
+
+    let Some(columns) = parse_column_names(columns, table, diagnostics) else {
+        return;
     };
 
     let key = Key::new(name, unique, table_name.clone(), columns);
 
-    let table = tables.get_mut(table_name).unwrap();
+    if primary {
+        // Primary key columns are implicitly `NOT NULL`.
+        for column_name in key.iter_column_names() {
+            if let Some(column) = table.columns.get_mut(column_name) {
+                column.nullable = false;
+            }
+        }
 
-    if matches!(index_type, sql_parse::IndexType::Primary(_)) {
         table.primary_key = Some(key);
-    } else {
+    } else if !table.keys.contains_key(&key.name) {
         table.key_names.push(key.name.clone());
         table.keys.insert(key.name.clone(), key);
     }
 }
+
+fn parse_add_foreign_key(
+    name: Option<Ident>,
+    columns: Vec<Ident>,
+    foreign_table: ObjectName,
+    referred_columns: Vec<Ident>,
+    table_name: &String,
+    schema: &mut Schema,
+    diagnostics: &mut Diagnostics,
+) {
+    let dialect = schema.dialect;
+    let foreign_table_name = parse_object_name(&foreign_table);
+
+    // Foreign keys without referred columns reference the primary key.
+    let foreign_column_names = match referred_columns.is_empty() {
+        true => match schema
+            .tables
+            .get(&foreign_table_name)
+            .and_then(|foreign_table| foreign_table.primary_key.as_ref())
+        {
+            Some(primary_key) => primary_key.column_names.clone(),
+            None => {
+                let message = format!("`{}` has no primary key to reference", foreign_table_name);
+                diagnostics.skip(foreign_table.span(), message);
+                return;
+            }
+        },
+        false => referred_columns
+            .into_iter()
+            .map(|column| column.value)
+            .collect(),
+    };
+
+    let table = schema.tables.get_mut(table_name).unwrap();
+
+    let Some(columns) = parse_column_names(columns, table, diagnostics) else {
+        return;
+    };
+
+    // MariaDB names anonymous foreign keys `<table>_ibfk_<n>`.
+    let name = name.map(|name| name.value).unwrap_or(format!(
+        "{}_ibfk_{}",
+        table_name,
+        table.foreign_key_names.len() + 1
+    ));
+
+    // MariaDB indexes the columns of foreign keys unless a key starts with them.
+    let indexed = table
+        .primary_key
+        .iter()
+        .chain(table.keys.values())
+        .any(|key| key.column_names.starts_with(&columns));
+    if dialect == SQLDialect::MariaDB && !indexed {
+        table.foreign_key_index_names.push(name.clone());
+    }
+
+    let foreign_key = ForeignKey::new(
+        name,
+        table_name.clone(),
+        columns,
+        foreign_table_name,
+        foreign_column_names,
+    );
+
+    table.foreign_key_names.push(foreign_key.name.clone());
+    table
+        .foreign_keys
+        .insert(foreign_key.name.clone(), foreign_key);
+}
+
+/// Parses SQL into a schema for tests, failing on errors.
+#[cfg(test)]
+pub(crate) fn parse_source(source: &str, dialect: SQLDialect) -> Schema {
+    let mut schema = Schema::new(dialect);
+    let mut diagnostics = Diagnostics::new(false);
+
+    parse_statements(source, &mut schema, &mut diagnostics);
+    assert!(!diagnostics.has_errors(), "{}", diagnostics);
+
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        database::{column::ColumnType, dialect::SQLDialect, schema::Schema},
+        diagnostics::Diagnostics,
+    };
+
+    use super::{parse_source, parse_statements};
+
+    fn parse_with_warnings(source: &str) -> (Schema, String) {
+        let mut schema = Schema::new(SQLDialect::MariaDB);
+        let mut diagnostics = Diagnostics::new(true);
+
+        parse_statements(source, &mut schema, &mut diagnostics);
+
+        (schema, diagnostics.to_string())
+    }
+
+    #[test]
+    fn keeps_the_name_of_enum_types() {
+        let schema = parse_source(
+            "CREATE TYPE mood AS ENUM ('sad', 'happy');
+            CREATE TABLE people (feeling mood, history mood[]);",
+            SQLDialect::PostgreSQL,
+        );
+        let table = &schema.tables["people"];
+        let values = vec!["sad".to_string(), "happy".to_string()];
+        let mood = ColumnType::Enum(values, Some("mood".to_string()));
+
+        assert_eq!(table.columns["feeling"].type_, mood);
+        assert_eq!(
+            table.columns["history"].type_,
+            ColumnType::Array(Box::new(mood))
+        );
+    }
+
+    #[test]
+    fn identity_columns_are_not_null() {
+        let schema = parse_source(
+            "CREATE TABLE things (code TEXT PRIMARY KEY, seq INTEGER GENERATED ALWAYS AS IDENTITY, id SERIAL);",
+            SQLDialect::PostgreSQL,
+        );
+        let table = &schema.tables["things"];
+
+        for column_name in ["seq", "id"] {
+            assert!(table.columns[column_name].auto_increment);
+            assert!(!table.columns[column_name].nullable);
+        }
+    }
+
+    #[test]
+    fn drops_the_index_of_a_dropped_foreign_key() {
+        let schema = parse_source(
+            "CREATE TABLE users (id INT NOT NULL, PRIMARY KEY (id));
+            CREATE TABLE orders (id INT NOT NULL, user_id INT NOT NULL, PRIMARY KEY (id), CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id));
+            ALTER TABLE orders DROP FOREIGN KEY fk_user;
+            DROP INDEX fk_user ON orders;",
+            SQLDialect::MariaDB,
+        );
+        let table = &schema.tables["orders"];
+
+        assert!(table.foreign_keys.is_empty());
+        assert!(table.foreign_key_index_names.is_empty());
+    }
+
+    #[test]
+    fn references_the_primary_key_without_columns() {
+        let schema = parse_source(
+            "CREATE TABLE users (id BIGINT PRIMARY KEY);
+            CREATE TABLE posts (id BIGINT PRIMARY KEY, author_id BIGINT REFERENCES users);",
+            SQLDialect::PostgreSQL,
+        );
+        let foreign_key = schema.tables["posts"].iter_foreign_keys().next().unwrap();
+
+        assert_eq!(foreign_key.foreign_column_names, ["id"]);
+    }
+
+    #[test]
+    fn changes_and_moves_a_column() {
+        let schema = parse_source(
+            "CREATE TABLE t (a INT, b INT);
+            ALTER TABLE t CHANGE a c TEXT NOT NULL AFTER b;",
+            SQLDialect::MariaDB,
+        );
+        let table = &schema.tables["t"];
+
+        assert_eq!(table.column_names, ["b", "c"]);
+        assert_eq!(table.columns["c"].type_, ColumnType::String);
+        assert!(!table.columns["c"].nullable);
+    }
+
+    #[test]
+    fn reports_a_column_moved_after_itself() {
+        let (schema, warnings) = parse_with_warnings(
+            "CREATE TABLE t (a INT, b INT); ALTER TABLE t MODIFY a INT AFTER a;",
+        );
+
+        assert_eq!(schema.tables["t"].column_names, ["a", "b"]);
+        assert_eq!(warnings, "warning: unknown column `a` in `t`\n\n");
+    }
+
+    #[test]
+    fn keeps_altering_after_a_conflicting_rename() {
+        let (schema, warnings) = parse_with_warnings(
+            "CREATE TABLE a (id INT); CREATE TABLE b (id INT);
+            ALTER TABLE a RENAME TO b, ADD COLUMN name TEXT;",
+        );
+
+        assert_eq!(schema.tables["a"].column_names, ["id", "name"]);
+        assert_eq!(warnings, "warning: table `b` already exists\n\n");
+    }
+}
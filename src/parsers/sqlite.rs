@@ -0,0 +1,306 @@
+use std::{collections::HashMap, fs::File, io::Read, path::Path};
+
+use rusqlite::{Connection, OpenFlags, Result};
+use sqlparser::{dialect::SQLiteDialect, tokenizer::Span};
+
+use crate::{
+    database::{
+        column::Column,
+        dialect::SQLDialect,
+        keys::{ForeignKey, Key},
+        schema::Schema,
+        table::Table,
+    },
+    diagnostics::Diagnostics,
+};
+
+use super::{parse_default_sql, parse_rowid_alias, parse_sqlite_type};
+
+/// Every SQLite database file starts with this header.
+const HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+pub(crate) fn is_database<P: AsRef<Path>>(path: P) -> bool {
+    let mut header = [0; 16];
+
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .is_ok()
+        && &header == HEADER
+}
+
+/// Rebuilds the schema of a SQLite database from `sqlite_master` and the table pragmas.
+pub(crate) fn read_database<P: AsRef<Path>>(
+    path: P,
+    diagnostics: &mut Diagnostics,
+) -> Result<Schema> {
+    let connection = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+    diagnostics.set_file(path.as_ref().display().to_string(), "");
+
+    let mut schema = Schema::new(SQLDialect::Sqlite);
+
+    let mut statement = connection.prepare(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY rowid",
+    )?;
+    let table_names = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<String>>>()?;
+
+    for table_name in table_names {
+        let table = read_table(&connection, table_name, diagnostics)?;
+
+        schema.tables.insert(table.name.clone(), table);
+    }
+
+    Ok(schema)
+}
+
+fn read_table(
+    connection: &Connection,
+    table_name: String,
+    diagnostics: &mut Diagnostics,
+) -> Result<Table> {
+    let mut table = Table::new(table_name, Vec::new(), HashMap::new());
+
+    read_columns(connection, &mut table)?;
+    read_indexes(connection, &mut table, diagnostics)?;
+    read_foreign_keys(connection, &mut table)?;
+
+    Ok(table)
+}
+
+fn read_columns(connection: &Connection, table: &mut Table) -> Result<()> {
+    let mut statement = connection.prepare(
+        r#"SELECT name, type, "notnull", dflt_value, pk FROM pragma_table_info(?1) ORDER BY cid"#,
+    )?;
+    let mut rows = statement.query([&table.name])?;
+
+    let mut declared_types = HashMap::new();
+    let mut primary_key = Vec::new();
+
+    while let Some(row) = rows.next()? {
+        let name: String = row.get(0)?;
+        let declared_type: String = row.get(1)?;
+        let not_null: bool = row.get(2)?;
+        let default: Option<String> = row.get(3)?;
+        let primary_key_index: usize = row.get(4)?;
+
+        // Primary key columns are implicitly `NOT NULL`.
+        let nullable = !not_null && primary_key_index == 0;
+
+        let column = Column::new(
+            name.clone(),
+            parse_sqlite_type(&declared_type),
+            nullable,
+            false,
+            "".into(),
+            default.map(|default| parse_default_sql(&SQLiteDialect {}, &default)),
+        );
+
+        if primary_key_index > 0 {
+            primary_key.push((primary_key_index, name.clone()));
+        }
+
+        declared_types.insert(name.clone(), declared_type);
+        table.column_names.push(name.clone());
+        table.columns.insert(name, column);
+    }
+
+    if !primary_key.is_empty() {
+        primary_key.sort();
+
+        let column_names = primary_key.into_iter().map(|(_, name)| name).collect();
+        table.primary_key = Some(Key::new("".into(), true, table.name.clone(), column_names));
+    }
+
+    parse_rowid_alias(table, &declared_types);
+
+    Ok(())
+}
+
+fn read_indexes(
+    connection: &Connection,
+    table: &mut Table,
+    diagnostics: &mut Diagnostics,
+) -> Result<()> {
+    let mut statement = connection
+        .prepare(r#"SELECT name, "unique", origin FROM pragma_index_list(?1) ORDER BY seq DESC"#)?;
+    let indexes = statement
+        .query_map([&table.name], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?
+        .collect::<Result<Vec<(String, bool, String)>>>()?;
+
+    for (index_name, unique, origin) in indexes {
+        // The primary key is already known from `table_info`.
+        if origin == "pk" {
+            continue;
+        }
+
+        let mut statement =
+            connection.prepare("SELECT name FROM pragma_index_info(?1) ORDER BY seqno")?;
+        let column_names = statement
+            .query_map([&index_name], |row| row.get(0))?
+            .collect::<Result<Option<Vec<String>>>>()?;
+
+        // Expressions don't have a column name.
+        let Some(column_names) = column_names else {
+            let message = format!(
+                "unsupported index expression in `{}` on `{}`",
+                index_name, table.name
+            );
+            diagnostics.skip(Span::empty(), message);
+            continue;
+        };
+
+        // `UNIQUE` constraints get an `sqlite_autoindex_*` name, use the first
+        // column like the DDL parser does.
+        let name = match origin.as_str() {
+            "u" => column_names[0].clone(),
+            _ => index_name,
+        };
+
+        if table.keys.contains_key(&name) {
+            continue;
+        }
+
+        let key = Key::new(name, unique, table.name.clone(), column_names);
+
+        table.key_names.push(key.name.clone());
+        table.keys.insert(key.name.clone(), key);
+    }
+
+    Ok(())
+}
+
+fn read_foreign_keys(connection: &Connection, table: &mut Table) -> Result<()> {
+    let mut statement = connection.prepare(
+        r#"SELECT id, seq, "table", "from", "to" FROM pragma_foreign_key_list(?1) ORDER BY id DESC, seq"#,
+    )?;
+    let mut rows = statement.query([&table.name])?;
+
+    let mut last_id = None;
+
+    while let Some(row) = rows.next()? {
+        let id: usize = row.get(0)?;
+        let index: usize = row.get(1)?;
+        let foreign_table_name: String = row.get(2)?;
+        let column_name: String = row.get(3)?;
+
+        // `REFERENCES table` without columns refers to the primary key.
+        let foreign_column_name = match row.get(4)? {
+            Some(foreign_column_name) => foreign_column_name,
+            None => read_primary_key(connection, &foreign_table_name)?
+                .into_iter()
+                .nth(index)
+                .unwrap_or_default(),
+        };
+
+        // Every column of a foreign key is its own row with the same id.
+        if last_id == Some(id) {
+            let foreign_key_name = table.foreign_key_names.last().unwrap();
+            let foreign_key = table.foreign_keys.get_mut(foreign_key_name).unwrap();
+
+            foreign_key.column_names.push(column_name);
+            foreign_key.foreign_column_names.push(foreign_column_name);
+            continue;
+        }
+        last_id = Some(id);
+
+        // Foreign keys are anonymous in SQLite, name them the way MariaDB does.
+        let name = format!("{}_ibfk_{}", table.name, table.foreign_key_names.len() + 1);
+
+        let foreign_key = ForeignKey::new(
+            name,
+            table.name.clone(),
+            vec![column_name],
+            foreign_table_name,
+            vec![foreign_column_name],
+        );
+
+        table.foreign_key_names.push(foreign_key.name.clone());
+        table
+            .foreign_keys
+            .insert(foreign_key.name.clone(), foreign_key);
+    }
+
+    Ok(())
+}
+
+fn read_primary_key(connection: &Connection, table_name: &str) -> Result<Vec<String>> {
+    let mut statement =
+        connection.prepare("SELECT name FROM pragma_table_info(?1) WHERE pk > 0 ORDER BY pk")?;
+
+    let column_names = statement
+        .query_map([table_name], |row| row.get(0))?
+        .collect::<Result<Vec<String>>>()?;
+
+    Ok(column_names)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use rusqlite::Connection;
+
+    use crate::{database::column::ColumnType, diagnostics::Diagnostics};
+
+    use super::{is_database, read_database};
+
+    #[test]
+    fn reads_tables_keys_and_foreign_keys() {
+        let path = env::temp_dir().join(format!("database-classes-{}.sqlite", process::id()));
+        let _ = fs::remove_file(&path);
+
+        let connection = Connection::open(&path).unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE, active BOOLEAN DEFAULT 1);
+                CREATE TABLE orders (
+                    id INTEGER PRIMARY KEY,
+                    user_id INTEGER NOT NULL REFERENCES users,
+                    total REAL,
+                    placed_at DATETIME DEFAULT CURRENT_TIMESTAMP
+                );",
+            )
+            .unwrap();
+        drop(connection);
+
+        assert!(is_database(&path));
+
+        let mut diagnostics = Diagnostics::new(false);
+        let schema = read_database(&path, &mut diagnostics).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(diagnostics.is_empty());
+
+        let users = &schema.tables["users"];
+        assert_eq!(users.column_names, ["id", "email", "active"]);
+        assert!(users.columns["id"].auto_increment);
+        assert!(!users.columns["email"].nullable);
+        assert!(matches!(users.columns["active"].type_, ColumnType::Boolean));
+        assert_eq!(users.primary_key.as_ref().unwrap().column_names, ["id"]);
+        assert_eq!(users.key_names, ["email"]);
+        assert!(users.keys["email"].unique);
+
+        let orders = &schema.tables["orders"];
+        assert!(matches!(orders.columns["total"].type_, ColumnType::Float));
+        assert_eq!(orders.foreign_key_names, ["orders_ibfk_1"]);
+
+        let foreign_key = &orders.foreign_keys["orders_ibfk_1"];
+        assert_eq!(foreign_key.column_names, ["user_id"]);
+        assert_eq!(foreign_key.foreign_table_name, "users");
+        assert_eq!(foreign_key.foreign_column_names, ["id"]);
+    }
+
+    #[test]
+    fn sql_files_are_not_databases() {
+        let path = env::temp_dir().join(format!("database-classes-{}.sql", process::id()));
+        fs::write(&path, "CREATE TABLE users (id INTEGER PRIMARY KEY);").unwrap();
+
+        assert!(!is_database(&path));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{database::dialect::SQLDialect, options::Options};
+
+/// The helpers the generated classes use, `getPDO()` has to be adjusted to
+/// the database.
+const BOILERPLATE: &str = include_str!("../../boilerplate.php");
+
+/// Writes the boilerplate, connecting to a placeholder database of the dialect.
+pub(crate) fn write_boilerplate(dialect: SQLDialect, options: &Options) -> String {
+    BOILERPLATE.replace("{{connection}}", &write_connection(dialect, options))
+}
+
+/// The arguments of `new PDO()`, with the attributes for native types when asked.
+fn write_connection(dialect: SQLDialect, options: &Options) -> String {
+    let mut buffer = String::new();
+
+    buffer += match dialect {
+        SQLDialect::MariaDB => "\"mysql:host=localhost;dbname=de-blauwe-loper\", \"root\", \"\"",
+        SQLDialect::PostgreSQL => "\"pgsql:host=localhost;dbname=database\", \"postgres\", \"\"",
+        SQLDialect::Sqlite => "\"sqlite:database.sqlite\", null, null",
+    };
+
+    if options.native_types {
+        buffer += ", array(\n";
+        buffer += "        PDO::ATTR_EMULATE_PREPARES => false,\n";
+        buffer += "        PDO::ATTR_STRINGIFY_FETCHES => false,\n";
+        buffer += "    )";
+    }
+
+    buffer
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{database::dialect::SQLDialect, options::Options};
+
+    use super::write_boilerplate;
+
+    #[test]
+    fn connects_to_the_dialect() {
+        let options = Options {
+            native_types: true,
+            ..Options::default()
+        };
+        let boilerplate = write_boilerplate(SQLDialect::PostgreSQL, &options);
+
+        assert!(boilerplate.contains("new PDO(\"pgsql:host=localhost;dbname=database\", "));
+        assert!(boilerplate.contains("PDO::ATTR_EMULATE_PREPARES => false"));
+        assert!(!boilerplate.contains("{{connection}}"));
+    }
+
+    #[test]
+    fn keeps_the_mariadb_connection() {
+        let boilerplate = write_boilerplate(SQLDialect::MariaDB, &Options::default());
+
+        assert!(boilerplate
+            .contains("new PDO(\"mysql:host=localhost;dbname=de-blauwe-loper\", \"root\", \"\");"));
+    }
+}
@@ -1,61 +1,878 @@
-use convert_case::{Case, Casing};
+use crate::{
+    database::{
+        column::{Column, ColumnDefault, ColumnType},
+        schema::Schema,
+    },
+    options::{Options, UnknownEnumValues},
+};
 
-use crate::database::column::{Column, ColumnType};
+use super::{
+    enums::enum_values,
+    naming::{write_enum_class_name, write_property_name},
+    php::{write_case_name, write_docblock_text, write_string},
+};
 
-pub(crate) fn write_full_comment<'a>(column: &'a Column) -> String {
+pub(crate) fn write_full_comment<'a>(column: &'a Column, options: &'a Options) -> String {
     let mut buffer = String::new();
 
-    if column.nullable {
+    if is_nullable(column, options) {
         buffer += "null|";
     }
     match column.type_ {
         ColumnType::Boolean => buffer += "bool",
         ColumnType::Int => buffer += "int",
         ColumnType::Float => buffer += "float",
-        ColumnType::String => buffer += "string",
-        ColumnType::Enum(_) => {
-            buffer += &column.name.to_case(Case::Pascal);
-            buffer += "Enum";
+        ColumnType::Decimal(_, _) => buffer += write_decimal_type(options),
+        ColumnType::String | ColumnType::Binary(_) => buffer += "string",
+        ColumnType::Json => buffer += write_json_type(column),
+        ColumnType::Date | ColumnType::Time | ColumnType::DateTime | ColumnType::Timestamp => {
+            buffer += "DateTimeImmutable"
+        }
+        ColumnType::Enum(..) => buffer += &write_enum_name(column),
+        ColumnType::Set(_) => {
+            buffer += "array<";
+            buffer += &write_enum_name(column);
+            buffer += ">";
+        }
+        ColumnType::Array(ref element) => {
+            buffer += "array<";
+            buffer += &write_element_type(column, element, options);
+            buffer += ">";
         }
     };
 
     buffer += " $";
-    buffer += &column.name.to_case(Case::Camel);
+    buffer += &write_property_name(column);
 
-    buffer += " ";
-    buffer += &column.comment;
+    if let Some(definition) = write_definition(&column.type_) {
+        buffer += " ";
+        buffer += &definition;
+    }
+
+    if !column.comment.is_empty() {
+        buffer += " ";
+        buffer += &write_docblock_text(&column.comment, "\t *     ");
+    }
 
     buffer
 }
 
-pub(crate) fn write_typed_variable<'a>(column: &'a Column) -> String {
+pub(crate) fn write_typed_variable<'a>(column: &'a Column, options: &'a Options) -> String {
     let mut buffer = String::new();
 
-    if column.nullable {
+    if is_nullable(column, options) {
         buffer += "null|";
     }
     match column.type_ {
         ColumnType::Boolean => buffer += "bool",
         ColumnType::Int => buffer += "int",
         ColumnType::Float => buffer += "float",
-        ColumnType::String => buffer += "string",
-        ColumnType::Enum(_) => {
-            buffer += &column.name.to_case(Case::Pascal);
-            buffer += "Enum";
+        ColumnType::Decimal(_, _) => buffer += write_decimal_type(options),
+        ColumnType::String | ColumnType::Binary(_) => buffer += "string",
+        ColumnType::Json => buffer += write_json_type(column),
+        ColumnType::Date | ColumnType::Time | ColumnType::DateTime | ColumnType::Timestamp => {
+            buffer += "DateTimeImmutable"
         }
+        ColumnType::Enum(..) => buffer += &write_enum_name(column),
+        ColumnType::Set(_) | ColumnType::Array(_) => buffer += "array",
     };
 
     buffer += " $";
-    buffer += &column.name.to_case(Case::Camel);
+    buffer += &write_property_name(column);
 
     buffer
 }
 
-pub(crate) fn write_variable<'a>(column: &'a Column) -> String {
+fn write_element_type<'a>(
+    column: &'a Column,
+    element: &'a ColumnType,
+    options: &'a Options,
+) -> String {
+    match element {
+        ColumnType::Boolean => "bool".into(),
+        ColumnType::Int => "int".into(),
+        ColumnType::Float => "float".into(),
+        ColumnType::Decimal(_, _) => write_decimal_type(options).into(),
+        ColumnType::Json => write_json_type(column).into(),
+        ColumnType::String | ColumnType::Binary(_) => "string".into(),
+        ColumnType::Date | ColumnType::Time | ColumnType::DateTime | ColumnType::Timestamp => {
+            "DateTimeImmutable".into()
+        }
+        ColumnType::Enum(..) => write_enum_name(column),
+        ColumnType::Set(_) => format!("array<{}>", write_enum_name(column)),
+        ColumnType::Array(element) => {
+            format!("array<{}>", write_element_type(column, element, options))
+        }
+    }
+}
+
+/// The `null` strategy for unknown enum values makes enum properties nullable.
+pub(crate) fn is_nullable<'a>(column: &'a Column, options: &'a Options) -> bool {
+    column.nullable
+        || matches!(column.type_, ColumnType::Enum(..))
+            && matches!(options.unknown_enum_values, UnknownEnumValues::Null)
+}
+
+pub(crate) fn write_enum_name(column: &Column) -> String {
+    match &column.enum_name {
+        Some(name) => name.clone(),
+        None => write_enum_class_name(&column.name),
+    }
+}
+
+/// Writes the conversion of `value` to a case of the enum of a column.
+fn write_enum_decoder<'a>(column: &'a Column, value: &'a str, options: &'a Options) -> String {
+    let mut buffer = String::new();
+
+    let name = write_enum_name(column);
+
+    buffer += &name;
+    buffer += "::tryFrom(";
+    buffer += value;
+    buffer += ")";
+
+    match &options.unknown_enum_values {
+        UnknownEnumValues::Null => (),
+        UnknownEnumValues::Fallback(fallback) if has_enum_value(&column.type_, fallback) => {
+            buffer += " ?? ";
+            buffer += &name;
+            buffer += "::";
+            buffer += &write_case_name(enum_values(&column.type_).unwrap(), fallback);
+        }
+        UnknownEnumValues::Exception | UnknownEnumValues::Fallback(_) => {
+            buffer += " ?? throw new UnknownEnumValueException(";
+            buffer += &name;
+            buffer += "::class, ";
+            buffer += value;
+            buffer += ")";
+        }
+    }
+
+    buffer
+}
+
+fn has_enum_value<'a>(type_: &'a ColumnType, value: &'a str) -> bool {
+    match type_ {
+        ColumnType::Enum(values, _) | ColumnType::Set(values) => values.iter().any(|v| v == value),
+        ColumnType::Array(element) => has_enum_value(element, value),
+        _ => false,
+    }
+}
+
+/// Decimals are strings, unless a value-object class is configured.
+fn write_decimal_type(options: &Options) -> &str {
+    options.decimal_class.as_deref().unwrap_or("string")
+}
+
+/// A `BINARY(16)` column configured to hold a UUID.
+fn is_uuid(column: &Column) -> bool {
+    matches!(column.type_, ColumnType::Binary(Some(16))) && column.uuid
+}
+
+/// JSON is decoded into arrays, unless a class is configured for the column.
+fn write_json_type(column: &Column) -> &str {
+    column.json_class.as_deref().unwrap_or("array")
+}
+
+/// Applies the options configured by `table.column` to the columns.
+pub(crate) fn configure_columns<'a>(schema: &'a mut Schema, options: &'a Options) {
+    for table in schema.tables.values_mut() {
+        for column in table.columns.values_mut() {
+            let path = format!("{}.{}", table.name, column.name);
+
+            column.json_class = options.json_classes.get(&path).cloned();
+            column.uuid = options.uuid_columns.contains(&path);
+        }
+    }
+}
+
+/// Writes the SQL definition of types the PHP type doesn't fully describe,
+/// like `DECIMAL(10,2)` or `SET('read','write')`.
+fn write_definition(type_: &ColumnType) -> Option<String> {
+    match type_ {
+        ColumnType::Decimal(Some(precision), Some(scale)) => {
+            Some(format!("DECIMAL({},{})", precision, scale))
+        }
+        ColumnType::Decimal(_, _) => Some("DECIMAL".into()),
+        ColumnType::Set(values) => {
+            let values = values
+                .iter()
+                .map(|value| format!("'{}'", value.replace('\'', "''")))
+                .collect::<Vec<_>>();
+
+            Some(format!("SET({})", values.join(",")))
+        }
+        ColumnType::Array(element) => write_definition(element),
+        _ => None,
+    }
+}
+
+/// Writes the conversion of a column in `$row` to its PHP value.
+pub(crate) fn write_row_value<'a>(column: &'a Column, options: &'a Options) -> String {
+    let mut buffer = String::new();
+
+    let row = format!("$row[{}]", write_string(&column.name));
+
+    match column.type_ {
+        ColumnType::Enum(..) => {
+            if column.nullable {
+                buffer += &row;
+                buffer += " === null ? null : ";
+            }
+
+            buffer += &write_enum_decoder(column, &row, options);
+        }
+        ColumnType::Array(ref element) => {
+            buffer += &write_array_decoder(column, element, options);
+        }
+        ColumnType::Decimal(_, _) => match &options.decimal_class {
+            Some(class) => {
+                if column.nullable {
+                    buffer += &row;
+                    buffer += " === null ? null : ";
+                }
+
+                buffer += class;
+                buffer += "::of(";
+                buffer += &row;
+                buffer += ")";
+            }
+            None => buffer += &write_cast(column, &row, "string"),
+        },
+        ColumnType::Json => {
+            if column.nullable {
+                buffer += &row;
+                buffer += " === null ? null : ";
+            }
+
+            buffer += &write_json_decoder(column, &row);
+        }
+        // An empty set is an empty string, not an empty member.
+        ColumnType::Set(_) => {
+            if column.nullable {
+                buffer += &row;
+                buffer += " === null ? null : (";
+            }
+
+            buffer += &row;
+            buffer += " === \"\" ? array() : array_map(fn ($value) => ";
+            buffer += &write_enum_decoder(column, "$value", options);
+            buffer += ", explode(\",\", ";
+            buffer += &row;
+            buffer += "))";
+
+            if column.nullable {
+                buffer += ")";
+            }
+        }
+        ColumnType::Binary(_) => {
+            if column.nullable {
+                buffer += &row;
+                buffer += " === null ? null : ";
+            }
+
+            if is_uuid(column) {
+                buffer += "decodeUuid(";
+            } else {
+                buffer += "decodeBinary(";
+            }
+            buffer += &row;
+            buffer += ")";
+        }
+        ColumnType::Date | ColumnType::Time | ColumnType::DateTime | ColumnType::Timestamp => {
+            if column.nullable {
+                buffer += &row;
+                buffer += " === null ? null : ";
+            }
+
+            buffer += "new DateTimeImmutable(";
+            buffer += &row;
+            if let (ColumnType::Timestamp, Some(timezone)) = (&column.type_, &options.timezone) {
+                buffer += ", new DateTimeZone(";
+                buffer += &write_string(timezone);
+                buffer += ")";
+            }
+            buffer += ")";
+        }
+        // Native types still return integers for MariaDB and SQLite booleans.
+        ColumnType::Boolean => buffer += &write_cast(column, &row, "bool"),
+        ColumnType::Int if !options.native_types => buffer += &write_cast(column, &row, "int"),
+        ColumnType::Float if !options.native_types => buffer += &write_cast(column, &row, "float"),
+        _ => buffer += &row,
+    }
+
+    buffer
+}
+
+/// Writes a cast that leaves `null` alone, PDO returns strings unless it
+/// uses native types.
+fn write_cast<'a>(column: &'a Column, row: &'a str, type_: &'a str) -> String {
+    let mut buffer = String::new();
+
+    if column.nullable {
+        buffer += row;
+        buffer += " === null ? null : ";
+    }
+
+    buffer += "(";
+    buffer += type_;
+    buffer += ")";
+    buffer += row;
+
+    buffer
+}
+
+/// Writes the conversion of a PostgreSQL array literal in `$row` to a PHP array.
+pub(crate) fn write_array_decoder<'a>(
+    column: &'a Column,
+    element: &'a ColumnType,
+    options: &'a Options,
+) -> String {
+    let mut buffer = String::new();
+
+    let row = format!("$row[{}]", write_string(&column.name));
+
+    if column.nullable {
+        buffer += &row;
+        buffer += " === null ? null : ";
+    }
+
+    match element {
+        ColumnType::Boolean => {
+            buffer += "array_map(fn ($value) => $value === \"t\", decodeArray(";
+            buffer += &row;
+            buffer += "))";
+        }
+        ColumnType::Int => {
+            buffer += "array_map(intval(...), decodeArray(";
+            buffer += &row;
+            buffer += "))";
+        }
+        ColumnType::Float => {
+            buffer += "array_map(floatval(...), decodeArray(";
+            buffer += &row;
+            buffer += "))";
+        }
+        ColumnType::Enum(..) => {
+            buffer += "array_map(fn ($value) => ";
+            buffer += &write_enum_decoder(column, "$value", options);
+            buffer += ", decodeArray(";
+            buffer += &row;
+            buffer += "))";
+        }
+        ColumnType::Date | ColumnType::Time | ColumnType::DateTime | ColumnType::Timestamp => {
+            buffer += "array_map(fn ($value) => new DateTimeImmutable($value), decodeArray(";
+            buffer += &row;
+            buffer += "))";
+        }
+        ColumnType::Decimal(_, _) if options.decimal_class.is_some() => {
+            buffer += "array_map(";
+            buffer += write_decimal_type(options);
+            buffer += "::of(...), decodeArray(";
+            buffer += &row;
+            buffer += "))";
+        }
+        ColumnType::Json => {
+            buffer += "array_map(fn ($value) => ";
+            buffer += &write_json_decoder(column, "$value");
+            buffer += ", decodeArray(";
+            buffer += &row;
+            buffer += "))";
+        }
+        ColumnType::Decimal(_, _)
+        | ColumnType::String
+        | ColumnType::Binary(_)
+        | ColumnType::Set(_)
+        | ColumnType::Array(_) => {
+            buffer += "decodeArray(";
+            buffer += &row;
+            buffer += ")";
+        }
+    }
+
+    buffer
+}
+
+fn write_json_decoder<'a>(column: &'a Column, value: &'a str) -> String {
+    let mut buffer = String::new();
+
+    let class = &column.json_class;
+
+    if let Some(class) = class {
+        buffer += class;
+        buffer += "::fromArray(";
+    }
+
+    buffer += "json_decode(";
+    buffer += value;
+    buffer += ", true, flags: JSON_THROW_ON_ERROR)";
+
+    if class.is_some() {
+        buffer += ")";
+    }
+
+    buffer
+}
+
+/// Writes the value bound to the query parameter of a column, from its
+/// variable or another value like `$this->id`.
+pub(crate) fn write_parameter_value_of<'a>(
+    column: &'a Column,
+    value: &'a str,
+    options: &'a Options,
+) -> String {
+    let mut buffer = String::new();
+
+    match column.type_ {
+        ColumnType::Date | ColumnType::Time | ColumnType::DateTime | ColumnType::Timestamp => {
+            buffer += value;
+            if column.nullable {
+                buffer += "?";
+            }
+
+            // Timestamps are passed in the time zone of the application.
+            if let (ColumnType::Timestamp, Some(timezone)) = (&column.type_, &options.timezone) {
+                buffer += "->setTimezone(new DateTimeZone(";
+                buffer += &write_string(timezone);
+                buffer += "))";
+            }
+
+            buffer += "->format(\"";
+            buffer += write_date_format(&column.type_);
+            buffer += "\")";
+        }
+        ColumnType::Array(ref element) => {
+            if column.nullable {
+                buffer += value;
+                buffer += " === null ? null : ";
+            }
+            buffer += "encodeArray(";
+            if let ColumnType::Json = **element {
+                buffer += "array_map(fn ($value) => json_encode($value, JSON_THROW_ON_ERROR), ";
+                buffer += value;
+                buffer += ")";
+            } else {
+                buffer += value;
+            }
+            buffer += ")";
+        }
+        // PDO can't bind enums, only their values.
+        ColumnType::Enum(..) => {
+            buffer += value;
+            if is_nullable(column, options) {
+                buffer += "?";
+            }
+            buffer += "->value";
+        }
+        ColumnType::Set(_) => {
+            if column.nullable {
+                buffer += value;
+                buffer += " === null ? null : ";
+            }
+            buffer += "implode(\",\", array_map(fn ($value) => $value->value, ";
+            buffer += value;
+            buffer += "))";
+        }
+        ColumnType::Binary(_) if is_uuid(column) => {
+            if column.nullable {
+                buffer += value;
+                buffer += " === null ? null : ";
+            }
+            buffer += "encodeUuid(";
+            buffer += value;
+            buffer += ")";
+        }
+        ColumnType::Json => {
+            if column.nullable {
+                buffer += value;
+                buffer += " === null ? null : ";
+            }
+            buffer += "json_encode(";
+            buffer += value;
+            buffer += ", JSON_THROW_ON_ERROR)";
+        }
+        // Value objects are passed as their string representation.
+        ColumnType::Decimal(_, _) if options.decimal_class.is_some() => {
+            if column.nullable {
+                buffer += value;
+                buffer += " === null ? null : ";
+            }
+            buffer += "(string)";
+            buffer += value;
+        }
+        _ => buffer += value,
+    }
+
+    buffer
+}
+
+fn write_date_format(type_: &ColumnType) -> &'static str {
+    match type_ {
+        ColumnType::Date => "Y-m-d",
+        ColumnType::Time => "H:i:s",
+        _ => "Y-m-d H:i:s",
+    }
+}
+
+/// Writes the PHP value of a literal default, `None` when the default has to be
+/// left to the database.
+pub(crate) fn write_default_value<'a>(column: &'a Column, options: &'a Options) -> Option<String> {
+    let value = match (&column.type_, column.default.as_ref()?) {
+        (_, ColumnDefault::Null) if column.nullable => "null".into(),
+        (ColumnType::Boolean, ColumnDefault::Boolean(value)) => value.to_string(),
+        (ColumnType::Boolean, ColumnDefault::Number(value) | ColumnDefault::String(value)) => {
+            match value.to_lowercase().as_str() {
+                "0" | "f" | "false" => "false".into(),
+                "1" | "t" | "true" => "true".into(),
+                _ => return None,
+            }
+        }
+        (ColumnType::Int, ColumnDefault::Boolean(value)) => (*value as u8).to_string(),
+        (ColumnType::Int, ColumnDefault::Number(value) | ColumnDefault::String(value)) => {
+            value.parse::<i64>().ok()?.to_string()
+        }
+        (ColumnType::Float, ColumnDefault::Number(value) | ColumnDefault::String(value)) => {
+            format!("{:?}", value.parse::<f64>().ok()?)
+        }
+        // Value objects can't be created in a parameter default.
+        (
+            ColumnType::Decimal(_, _),
+            ColumnDefault::Number(value) | ColumnDefault::String(value),
+        ) if options.decimal_class.is_none() => {
+            value.parse::<f64>().ok()?;
+            write_string(value)
+        }
+        (ColumnType::Decimal(_, _), _) if write_decimal_default(column, options).is_some() => {
+            "null".into()
+        }
+        // Only empty documents are written, other JSON defaults make the parameter required.
+        (ColumnType::Json, ColumnDefault::String(value))
+            if column.json_class.is_none()
+                && matches!(value.replace(' ', "").as_str(), "[]" | "{}") =>
+        {
+            "array()".into()
+        }
+        (ColumnType::String, ColumnDefault::Number(value) | ColumnDefault::String(value)) => {
+            write_string(value)
+        }
+        // MariaDB's zero dates can't be represented.
+        (
+            ColumnType::Date | ColumnType::Time | ColumnType::DateTime | ColumnType::Timestamp,
+            ColumnDefault::String(value),
+        ) if !value.starts_with("0000") => {
+            let mut buffer = String::new();
+
+            buffer += "new DateTimeImmutable(";
+            buffer += &write_string(value);
+            buffer += ")";
+
+            buffer
+        }
+        (ColumnType::Enum(values, _), ColumnDefault::String(value)) if values.contains(value) => {
+            let mut buffer = String::new();
+
+            buffer += &write_enum_name(column);
+            buffer += "::";
+            buffer += &write_case_name(values, value);
+
+            buffer
+        }
+        (ColumnType::Set(values), ColumnDefault::String(value)) => {
+            let members = match value.is_empty() {
+                true => Vec::new(),
+                false => value.split(',').collect(),
+            };
+
+            let mut buffer = String::new();
+
+            buffer += "array(";
+            for (index, member) in members.into_iter().enumerate() {
+                if !values.iter().any(|value| value == member) {
+                    return None;
+                }
+
+                if index > 0 {
+                    buffer += ", ";
+                }
+                buffer += &write_enum_name(column);
+                buffer += "::";
+                buffer += &write_case_name(values, member);
+            }
+            buffer += ")";
+
+            buffer
+        }
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// The literal default of a decimal column when decimals are value objects.
+/// Those can't be parameter defaults, so the parameter defaults to `null`, which
+/// stands for this value.
+pub(crate) fn write_decimal_default<'a>(
+    column: &'a Column,
+    options: &'a Options,
+) -> Option<&'a str> {
+    match (&column.type_, column.default.as_ref()?) {
+        (
+            ColumnType::Decimal(_, _),
+            ColumnDefault::Number(value) | ColumnDefault::String(value),
+        ) if options.decimal_class.is_some()
+            && !column.nullable
+            && value.parse::<f64>().is_ok() =>
+        {
+            Some(value)
+        }
+        _ => None,
+    }
+}
+
+/// Columns the database fills in by itself are left out of the `INSERT`. Other
+/// defaults are passed, literal ones PHP can't write make the parameter required.
+pub(crate) fn is_inserted(column: &Column) -> bool {
+    !column.auto_increment && !column.generated && !has_default_expression(column)
+}
+
+/// Columns with a default the database evaluates, like `CURRENT_TIMESTAMP` or
+/// `uuid()`. They are only inserted when a value is passed.
+pub(crate) fn has_default_expression(column: &Column) -> bool {
+    !column.auto_increment
+        && !column.generated
+        && matches!(column.default, Some(ColumnDefault::Expression(_)))
+}
+
+/// Whether the parameter of a column can be left out, its default written in PHP.
+pub(crate) fn has_default_value<'a>(column: &'a Column, options: &'a Options) -> bool {
+    write_default_value(column, options).is_some()
+}
+
+pub(crate) fn write_variable(column: &Column) -> String {
     let mut buffer = String::new();
 
     buffer += "$";
-    buffer += &column.name.to_case(Case::Camel);
+    buffer += &write_property_name(column);
 
     buffer
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        database::{
+            column::{Column, ColumnDefault, ColumnType},
+            dialect::SQLDialect,
+        },
+        options::Options,
+        parsers::parse_source,
+    };
+
+    use super::{
+        configure_columns, write_default_value, write_full_comment, write_parameter_value_of,
+        write_row_value, write_typed_variable,
+    };
+
+    fn parse_options(args: &[&str]) -> Options {
+        Options::parse(args.iter().map(|arg| arg.to_string())).unwrap()
+    }
+
+    #[test]
+    fn converts_timestamps_in_the_time_zone() {
+        let column = Column::new(
+            "created_at".into(),
+            ColumnType::Timestamp,
+            true,
+            false,
+            "".into(),
+            None,
+        );
+        let options = parse_options(&["schema.sql", "--timezone", "Europe/Amsterdam"]);
+
+        assert_eq!(
+            write_row_value(&column, &options),
+            "$row[\"created_at\"] === null ? null : new DateTimeImmutable($row[\"created_at\"], new DateTimeZone(\"Europe/Amsterdam\"))"
+        );
+        assert_eq!(
+            write_parameter_value_of(&column, "$createdAt", &options),
+            "$createdAt?->setTimezone(new DateTimeZone(\"Europe/Amsterdam\"))->format(\"Y-m-d H:i:s\")"
+        );
+    }
+
+    #[test]
+    fn formats_dates_and_times_without_a_time_zone() {
+        let options = parse_options(&["schema.sql", "--timezone", "UTC"]);
+
+        let date = Column::new(
+            "born_on".into(),
+            ColumnType::Date,
+            false,
+            false,
+            "".into(),
+            Some(ColumnDefault::String("2000-01-01".into())),
+        );
+        let time = Column::new(
+            "opens_at".into(),
+            ColumnType::Time,
+            false,
+            false,
+            "".into(),
+            Some(ColumnDefault::String("0000-00-00".into())),
+        );
+
+        assert_eq!(
+            write_row_value(&date, &options),
+            "new DateTimeImmutable($row[\"born_on\"])"
+        );
+        assert_eq!(
+            write_parameter_value_of(&date, "$bornOn", &options),
+            "$bornOn->format(\"Y-m-d\")"
+        );
+        assert_eq!(
+            write_parameter_value_of(&time, "$opensAt", &options),
+            "$opensAt->format(\"H:i:s\")"
+        );
+
+        assert_eq!(
+            write_default_value(&date, &options).as_deref(),
+            Some("new DateTimeImmutable(\"2000-01-01\")")
+        );
+        // Zero dates are left to the database.
+        assert_eq!(write_default_value(&time, &options), None);
+    }
+
+    #[test]
+    fn splits_and_joins_sets() {
+        let options = Options::default();
+        let values = vec!["read".to_string(), "write".to_string()];
+        let mut column = Column::new(
+            "permissions".into(),
+            ColumnType::Set(values),
+            false,
+            false,
+            "Who may use it".into(),
+            Some(ColumnDefault::String("read,write".into())),
+        );
+
+        assert_eq!(
+            write_full_comment(&column, &options),
+            "array<PermissionsEnum> $permissions SET('read','write') Who may use it"
+        );
+        assert_eq!(
+            write_row_value(&column, &options),
+            "$row[\"permissions\"] === \"\" ? array() : array_map(fn ($value) => PermissionsEnum::tryFrom($value) ?? throw new UnknownEnumValueException(PermissionsEnum::class, $value), explode(\",\", $row[\"permissions\"]))"
+        );
+        assert_eq!(
+            write_parameter_value_of(&column, "$permissions", &options),
+            "implode(\",\", array_map(fn ($value) => $value->value, $permissions))"
+        );
+        assert_eq!(
+            write_default_value(&column, &options).as_deref(),
+            Some("array(PermissionsEnum::READ, PermissionsEnum::WRITE)")
+        );
+
+        // The empty set is a valid default, unknown members are not.
+        column.default = Some(ColumnDefault::String("".into()));
+        assert_eq!(
+            write_default_value(&column, &options).as_deref(),
+            Some("array()")
+        );
+        column.default = Some(ColumnDefault::String("read,delete".into()));
+        assert_eq!(write_default_value(&column, &options), None);
+    }
+
+    #[test]
+    fn hydrates_enums_by_the_unknown_value_strategy() {
+        let values = vec![
+            "open".to_string(),
+            "closed".to_string(),
+            "unknown".to_string(),
+        ];
+        let column = Column::new(
+            "state".into(),
+            ColumnType::Enum(values, None),
+            true,
+            false,
+            "".into(),
+            None,
+        );
+
+        let exception = parse_options(&["schema.sql"]);
+        assert_eq!(
+            write_row_value(&column, &exception),
+            "$row[\"state\"] === null ? null : StateEnum::tryFrom($row[\"state\"]) ?? throw new UnknownEnumValueException(StateEnum::class, $row[\"state\"])"
+        );
+        assert_eq!(
+            write_parameter_value_of(&column, "$state", &exception),
+            "$state?->value"
+        );
+
+        let fallback = parse_options(&["schema.sql", "--unknown-enum-values", "fallback=unknown"]);
+        assert_eq!(
+            write_row_value(&column, &fallback),
+            "$row[\"state\"] === null ? null : StateEnum::tryFrom($row[\"state\"]) ?? StateEnum::UNKNOWN"
+        );
+    }
+
+    #[test]
+    fn makes_enums_nullable_for_the_null_strategy() {
+        let values = vec!["open".to_string(), "closed".to_string()];
+        let column = Column::new(
+            "state".into(),
+            ColumnType::Enum(values, None),
+            false,
+            false,
+            "".into(),
+            None,
+        );
+        let options = parse_options(&["schema.sql", "--unknown-enum-values", "null"]);
+
+        assert_eq!(
+            write_row_value(&column, &options),
+            "StateEnum::tryFrom($row[\"state\"])"
+        );
+        assert_eq!(
+            write_typed_variable(&column, &options),
+            "null|StateEnum $state"
+        );
+        assert!(Options::parse(
+            ["schema.sql", "--unknown-enum-values", "ignore"]
+                .map(String::from)
+                .into_iter()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn configures_json_classes_by_table_and_column() {
+        let mut schema = parse_source(
+            "CREATE TABLE users (settings JSON);
+            CREATE TABLE teams (settings JSON);",
+            SQLDialect::MariaDB,
+        );
+        let mut options = Options::default();
+        options
+            .json_classes
+            .insert("users.settings".to_string(), "UserSettings".to_string());
+        configure_columns(&mut schema, &options);
+
+        let class = |table_name: &str| &schema.tables[table_name].columns["settings"].json_class;
+        assert_eq!(class("users").as_deref(), Some("UserSettings"));
+        assert_eq!(class("teams"), &None);
+    }
+
+    #[test]
+    fn configures_uuids_by_table_and_column() {
+        let mut schema = parse_source(
+            "CREATE TABLE users (id BINARY(16));
+            CREATE TABLE teams (id BINARY(16));",
+            SQLDialect::MariaDB,
+        );
+        let mut options = Options::default();
+        options.uuid_columns.insert("users.id".to_string());
+        configure_columns(&mut schema, &options);
+
+        assert!(schema.tables["users"].columns["id"].uuid);
+        assert!(!schema.tables["teams"].columns["id"].uuid);
+    }
+}
@@ -1,180 +1,341 @@
-use convert_case::{Case, Casing};
-
-use crate::database::{column::Column, table::Table};
-
-use super::column::{write_full_comment, write_typed_variable, write_variable};
-
-pub(crate) fn write_creater<'a>(table: &'a Table) -> String {
+use crate::{
+    database::{column::Column, dialect::SQLDialect, keys::Key, table::Table},
+    options::Options,
+};
+
+use super::{
+    column::{
+        has_default_expression, has_default_value, is_inserted, is_nullable, write_decimal_default,
+        write_default_value, write_full_comment, write_parameter_value_of, write_typed_variable,
+        write_variable,
+    },
+    getters::write_getter_name,
+    naming::write_table_class_name,
+    params::{write_query_execute_of, write_query_execute_params, write_query_params_of},
+    php::write_string,
+    sql::{write_identifier, write_parameter_name},
+};
+
+/// Writes `register()`, which returns the stored object read back by a key.
+/// Without a key the database fully knows after the insert, like a primary key
+/// filled in by a default expression, it returns `void`.
+pub(crate) fn write_creater<'a>(
+    table: &'a Table,
+    dialect: SQLDialect,
+    options: &'a Options,
+) -> String {
     let mut buffer = String::new();
 
-    buffer += &write_comment(table);
+    buffer += &write_comment(table, options);
 
     buffer += "\tpublic static function register(";
 
-    buffer += &write_parameters(table);
+    buffer += &write_parameters(table, options);
 
     buffer += "): ";
-    buffer += &table.name.to_case(Case::Pascal);
+    match find_inserted_key(table) {
+        Some(_) => buffer += &write_table_class_name(table),
+        None => buffer += "void",
+    }
     buffer += "\n\t{\n";
 
-    buffer += &write_body(table);
+    buffer += &write_body(table, dialect, options);
 
     buffer += "\t}\n";
 
     buffer
 }
 
-fn write_comment<'a>(table: &'a Table) -> String {
+fn write_comment<'a>(table: &'a Table, options: &'a Options) -> String {
     let mut buffer = String::new();
 
     buffer += "\n\t/** Create a `";
-    buffer += &table.name.to_case(Case::Pascal);
+    buffer += &write_table_class_name(table);
     buffer += "`\n";
 
-    for column in table.iter_columns().filter(|column| !column.auto_increment) {
+    for column in iter_parameters(table, options) {
         buffer += "\t * @param ";
-        buffer += &write_full_comment(column);
+        buffer += &write_parameter_comment(column, options);
         buffer += "\n";
     }
 
-    buffer += "\t * @return ";
-    buffer += &table.name.to_case(Case::Pascal);
-    buffer += " The updated object.\n";
+    match find_inserted_key(table) {
+        Some(_) => {
+            buffer += "\t * @return ";
+            buffer += &write_table_class_name(table);
+            buffer += " The stored object.\n";
+        }
+        None => buffer += "\t * @return void The stored row has no key to read it back by.\n",
+    }
 
     buffer += "\t */\n";
 
     buffer
 }
 
-fn write_parameters<'a>(table: &'a Table) -> String {
+/// The key to read an inserted row back by. That is the primary key, unless
+/// the database fills in one of its columns, then a unique key is used.
+fn find_inserted_key(table: &Table) -> Option<&Key> {
+    // `lastInsertId()` only knows a single auto-increment column.
+    let is_auto_increment = table
+        .iter_columns()
+        .filter(|column| column.auto_increment)
+        .count()
+        == 1;
+
+    let is_known =
+        |column: &Column| is_inserted(column) || column.auto_increment && is_auto_increment;
+
+    table
+        .primary_key
+        .iter()
+        .chain(table.iter_keys().filter(|key| key.unique))
+        .find(|key| key.iter_columns(table).all(is_known))
+}
+
+/// The inserted columns, the ones with a default go last so they can be left out.
+fn iter_parameters<'a>(table: &'a Table, options: &'a Options) -> impl Iterator<Item = &'a Column> {
+    let (optional, required): (Vec<_>, Vec<_>) =
+        iter_inserted(table).partition(|column| is_optional(column, options));
+
+    required.into_iter().chain(optional)
+}
+
+/// The columns passed to the `INSERT`, the ones with a default expression only
+/// when they aren't `null`.
+fn iter_inserted(table: &Table) -> impl Iterator<Item = &Column> {
+    table
+        .iter_columns()
+        .filter(|column| is_inserted(column) || has_default_expression(column))
+}
+
+/// Whether the parameter of a column can be left out.
+pub(crate) fn is_optional<'a>(column: &'a Column, options: &'a Options) -> bool {
+    has_default_value(column, options) || has_default_expression(column)
+}
+
+/// Whether the parameter of a column defaults to `null` even though the column
+/// isn't nullable, standing for the default of the column.
+fn has_null_default<'a>(column: &'a Column, options: &'a Options) -> bool {
+    write_decimal_default(column, options).is_some()
+        || has_default_expression(column) && !is_nullable(column, options)
+}
+
+fn write_parameters<'a>(table: &'a Table, options: &'a Options) -> String {
     let mut buffer = String::new();
 
-    let mut columns = table.iter_columns().filter(|column| !column.auto_increment);
+    let mut columns = iter_parameters(table, options);
 
     if let Some(column) = columns.next() {
-        buffer += &write_typed_variable(column);
+        buffer += &write_parameter(column, options);
     }
 
     for column in columns {
         buffer += ", ";
-        buffer += &write_typed_variable(column);
+        buffer += &write_parameter(column, options);
     }
 
     buffer
 }
 
-fn write_body<'a>(table: &'a Table) -> String {
+/// The comment of a parameter, decimals and default expressions defaulting to
+/// `null` are nullable.
+pub(crate) fn write_parameter_comment<'a>(column: &'a Column, options: &'a Options) -> String {
     let mut buffer = String::new();
 
-    buffer += &write_query_params(table);
+    if has_null_default(column, options) {
+        buffer += "null|";
+    }
+    buffer += &write_full_comment(column, options);
 
-    buffer += &write_query_prepare(table);
-    buffer += "\t\t$sth->execute($params);\n\n";
+    buffer
+}
 
-    buffer += "\t\treturn Self::get(";
-    buffer += &write_object_arguments(table.iter_columns());
-    buffer += ");\n";
+/// A typed parameter, with the default of the column. Default expressions are
+/// left to the database with `null`.
+pub(crate) fn write_parameter<'a>(column: &'a Column, options: &'a Options) -> String {
+    let mut buffer = String::new();
+
+    if has_null_default(column, options) {
+        buffer += "null|";
+    }
+    buffer += &write_typed_variable(column, options);
+
+    if let Some(value) = write_default_value(column, options) {
+        buffer += " = ";
+        buffer += &value;
+    } else if has_default_expression(column) {
+        buffer += " = null";
+    }
 
     buffer
 }
 
-fn write_query_params<'a>(table: &'a Table) -> String {
+fn write_body<'a>(table: &'a Table, dialect: SQLDialect, options: &'a Options) -> String {
     let mut buffer = String::new();
 
-    let columns = table
-        .iter_columns()
-        .filter(|column| !column.auto_increment)
+    let values = iter_inserted(table)
+        .map(|column| (column, write_inserted_value(column, options)))
         .collect::<Vec<_>>();
 
-    if columns.len() == 1 {
-        let column = columns[0];
+    buffer += &write_insert(table, &values, dialect, options);
+    buffer += "\n";
 
-        buffer += "\t\t$params = array(\":";
-        buffer += &column.name;
-        buffer += "\" => $";
-        buffer += &column.name.to_case(Case::Camel);
+    // Read the row back, so values the database computed are included.
+    if let Some(key) = find_inserted_key(table) {
+        buffer += "\t\treturn Self::";
+        buffer += &write_getter_name(key);
+        buffer += "(";
+        buffer += &write_object_arguments(key.iter_columns(table));
         buffer += ");\n";
-    } else if columns.len() > 1 {
-        buffer += "\t\t$params = array(\n";
-        for column in columns {
-            buffer += "\t\t\t\":";
-            buffer += &column.name;
-            buffer += "\" => $";
-            buffer += &column.name.to_case(Case::Camel);
-            buffer += ",\n";
-        }
-        buffer += "\t\t);\n";
     }
 
     buffer
 }
 
-fn write_query_prepare<'a>(table: &'a Table) -> String {
+/// The inserted value of a column, its variable or the default of decimals
+/// left `null`.
+pub(crate) fn write_inserted_value<'a>(column: &'a Column, options: &'a Options) -> String {
     let mut buffer = String::new();
 
-    buffer += "\t\t$sth = getPDO()->prepare(\"INSERT INTO `";
-    buffer += &table.name;
-    buffer += "` (";
+    match write_decimal_default(column, options) {
+        Some(value) => {
+            buffer += "(";
+            buffer += &write_variable(column);
+            buffer += " ?? ";
+            buffer += &write_string(value);
+            buffer += ")";
+        }
+        None => buffer += &write_variable(column),
+    }
 
-    buffer += &write_query_create(table);
+    buffer
+}
 
-    buffer += ") VALUES (";
+/// Inserts the values into a table. Columns with a default expression are
+/// only inserted when their value isn't `null`, so the query is built at runtime.
+pub(crate) fn write_insert<'a>(
+    table: &'a Table,
+    values: &'a [(&'a Column, String)],
+    dialect: SQLDialect,
+    options: &'a Options,
+) -> String {
+    let mut buffer = String::new();
 
-    buffer += &write_query_values(table);
+    let (omitted, inserted): (Vec<_>, Vec<_>) = values
+        .iter()
+        .partition(|(column, _)| has_default_expression(column));
 
-    buffer += ");\");\n";
+    if omitted.is_empty() {
+        buffer += &write_query_params_of(values, options);
+        buffer += &write_query_prepare(table, values, dialect);
+        buffer += &write_query_execute_of(values, options);
 
-    buffer
-}
+        return buffer;
+    }
 
-fn write_query_create<'a>(table: &'a Table) -> String {
-    let mut buffer = String::new();
+    buffer += "\t\t$columns = array(";
+    for (index, (column, _)) in inserted.iter().enumerate() {
+        if index > 0 {
+            buffer += ", ";
+        }
+        buffer += "\"";
+        buffer += &write_identifier(dialect, &column.name);
+        buffer += "\"";
+    }
+    buffer += ");\n";
 
-    let mut columns = table.iter_columns().filter(|column| !column.auto_increment);
+    buffer += "\t\t$params = array(";
+    for (index, (column, value)) in inserted.iter().enumerate() {
+        if index > 0 {
+            buffer += ", ";
+        }
+        buffer += "\":";
+        buffer += &write_parameter_name(&column.name);
+        buffer += "\" => ";
+        buffer += &write_parameter_value_of(column, value, options);
+    }
+    buffer += ");\n";
 
-    if let Some(column) = columns.next() {
-        buffer += "`";
-        buffer += &column.name;
-        buffer += "`";
+    for (column, value) in &omitted {
+        buffer += "\t\tif (";
+        buffer += value;
+        buffer += " !== null) {\n";
+        buffer += "\t\t\t$columns[] = \"";
+        buffer += &write_identifier(dialect, &column.name);
+        buffer += "\";\n";
+        buffer += "\t\t\t$params[\":";
+        buffer += &write_parameter_name(&column.name);
+        buffer += "\"] = ";
+        buffer += &write_parameter_value_of(column, value, options);
+        buffer += ";\n";
+        buffer += "\t\t}\n";
     }
 
-    for column in columns {
-        buffer += ", `";
-        buffer += &column.name;
-        buffer += "`";
+    buffer += "\t\t$sth = getPDO()->prepare(";
+    // Only MariaDB accepts an empty column list.
+    if dialect != SQLDialect::MariaDB && inserted.is_empty() {
+        buffer += "empty($columns) ? \"INSERT INTO ";
+        buffer += &write_identifier(dialect, &table.name);
+        buffer += " DEFAULT VALUES;\" : ";
     }
+    buffer += "\"INSERT INTO ";
+    buffer += &write_identifier(dialect, &table.name);
+    buffer += " (\" . implode(\", \", $columns) . \") VALUES (\" . implode(\", \", array_keys($params)) . \");\");\n";
+
+    buffer += &write_query_execute_params(values.iter().map(|(column, _)| *column));
 
     buffer
 }
 
-fn write_query_values<'a>(table: &'a Table) -> String {
+fn write_query_prepare<'a>(
+    table: &'a Table,
+    values: &'a [(&'a Column, String)],
+    dialect: SQLDialect,
+) -> String {
     let mut buffer = String::new();
 
-    let mut columns = table.iter_columns().filter(|column| !column.auto_increment);
+    buffer += "\t\t$sth = getPDO()->prepare(\"INSERT INTO ";
+    buffer += &write_identifier(dialect, &table.name);
 
-    if let Some(column) = columns.next() {
-        buffer += ":";
-        buffer += &column.name;
+    // Only MariaDB accepts an empty column list.
+    if dialect != SQLDialect::MariaDB && values.is_empty() {
+        buffer += " DEFAULT VALUES;\");\n";
+        return buffer;
     }
 
-    for column in columns {
-        buffer += ", :";
-        buffer += &column.name;
+    buffer += " (";
+
+    for (index, (column, _)) in values.iter().enumerate() {
+        if index > 0 {
+            buffer += ", ";
+        }
+        buffer += &write_identifier(dialect, &column.name);
     }
 
+    buffer += ") VALUES (";
+
+    for (index, (column, _)) in values.iter().enumerate() {
+        if index > 0 {
+            buffer += ", ";
+        }
+        buffer += ":";
+        buffer += &write_parameter_name(&column.name);
+    }
+
+    buffer += ");\");\n";
+
     buffer
 }
-fn write_object_arguments<'a>(
-    mut columns: impl Iterator<Item = &'a Column>,
-    // parameter_column_names: &'a Vec<String>,
-) -> String {
+
+fn write_object_arguments<'a>(mut columns: impl Iterator<Item = &'a Column>) -> String {
     let mut buffer = String::new();
 
     if let Some(column) = columns.next() {
         if column.auto_increment {
             buffer += "(int)getPDO()->lastInsertId()";
         } else {
-            buffer += &write_variable(&column);
+            buffer += &write_variable(column);
         }
     }
 
@@ -184,9 +345,90 @@ fn write_object_arguments<'a>(
         if column.auto_increment {
             buffer += "(int)getPDO()->lastInsertId()";
         } else {
-            buffer += &write_variable(&column);
+            buffer += &write_variable(column);
         }
     }
 
     buffer
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{database::dialect::SQLDialect, options::Options, parsers::parse_source};
+
+    use super::write_creater;
+
+    #[test]
+    fn passes_literal_defaults_php_cannot_write() {
+        let schema = parse_source(
+            "CREATE TABLE posts (id SERIAL PRIMARY KEY, tags TEXT[] NOT NULL DEFAULT '{}', created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP);",
+            SQLDialect::PostgreSQL,
+        );
+        let creater = write_creater(
+            &schema.tables["posts"],
+            SQLDialect::PostgreSQL,
+            &Options::default(),
+        );
+
+        assert!(creater
+            .contains("register(array $tags, null|DateTimeImmutable $createdAt = null): Posts"));
+        assert!(creater.contains("$columns = array(\"\\\"tags\\\"\");"));
+        assert!(creater.contains(
+            "if ($createdAt !== null) {
+\t\t\t$columns[] = \"\\\"created_at\\\"\";
+\t\t\t$params[\":created_at\"] = $createdAt->format(\"Y-m-d H:i:s\");
+\t\t}"
+        ));
+    }
+
+    #[test]
+    fn inserts_default_values_when_no_column_is_passed() {
+        let schema = parse_source(
+            "CREATE TABLE tokens (value UUID PRIMARY KEY DEFAULT gen_random_uuid());",
+            SQLDialect::PostgreSQL,
+        );
+        let creater = write_creater(
+            &schema.tables["tokens"],
+            SQLDialect::PostgreSQL,
+            &Options::default(),
+        );
+
+        assert!(creater.contains("register(null|string $value = null): void"));
+        assert!(creater.contains(
+            "prepare(empty($columns) ? \"INSERT INTO \\\"tokens\\\" DEFAULT VALUES;\" : \"INSERT INTO"
+        ));
+    }
+
+    #[test]
+    fn inserts_the_default_of_decimal_objects_left_null() {
+        let schema = parse_source(
+            "CREATE TABLE accounts (id INT NOT NULL PRIMARY KEY, balance DECIMAL(10,2) NOT NULL DEFAULT 0.00);",
+            SQLDialect::MariaDB,
+        );
+        let options = Options {
+            decimal_class: Some("Decimal".to_string()),
+            ..Options::default()
+        };
+        let creater = write_creater(&schema.tables["accounts"], SQLDialect::MariaDB, &options);
+
+        assert!(creater.contains("register(int $id, null|Decimal $balance = null)"));
+        assert!(creater.contains("\":balance\" => (string)($balance ?? \"0.00\")"));
+    }
+
+    #[test]
+    fn returns_nothing_without_a_known_key() {
+        let schema = parse_source(
+            "CREATE TABLE sessions (id UUID NOT NULL DEFAULT uuid() PRIMARY KEY, data TEXT NOT NULL);",
+            SQLDialect::MariaDB,
+        );
+        let creater = write_creater(
+            &schema.tables["sessions"],
+            SQLDialect::MariaDB,
+            &Options::default(),
+        );
+
+        assert!(creater.contains("@return void The stored row has no key to read it back by."));
+        assert!(creater.contains("register(string $data, null|string $id = null): void"));
+        assert!(!creater.contains("return Self::"));
+    }
+}
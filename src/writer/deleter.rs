@@ -1,39 +1,78 @@
-use convert_case::{Case, Casing};
-
-use crate::database::{column::Column, keys::Key, table::Table};
-
-use super::column::{write_full_comment, write_typed_variable};
-
-pub(crate) fn write_deleter<'a>(table: &'a Table) -> String {
+use crate::{
+    database::{column::Column, dialect::SQLDialect, keys::Key, table::Table},
+    options::Options,
+};
+
+use super::{
+    column::{write_full_comment, write_typed_variable},
+    naming::{write_property_name, write_table_class_name},
+    params::{write_query_execute, write_query_params},
+    sql::{write_identifier, write_parameter_name},
+};
+
+pub(crate) fn write_deleter<'a>(
+    table: &'a Table,
+    dialect: SQLDialect,
+    options: &'a Options,
+) -> String {
     let mut buffer = String::new();
 
     let primary_key = table.primary_key.as_ref().unwrap();
-    buffer += &write_comment(table, primary_key);
+    buffer += &write_comment(table, primary_key, options);
 
-    buffer += "\tpublic static function delete(";
+    buffer += "\tpublic static function deleteByKey(";
 
-    buffer += &write_parameters(table, &primary_key);
+    buffer += &write_parameters(table, primary_key, options);
 
     buffer += "): void\n";
     buffer += "\t{\n";
 
-    buffer += &write_body(table, &primary_key);
+    buffer += &write_body(table, primary_key, dialect, options);
 
     buffer += "\t}\n";
 
+    buffer += &write_instance_deleter(table, primary_key);
+
     buffer
 }
 
-fn write_comment<'a>(table: &'a Table, key: &'a Key) -> String {
+/// Deletes an object with `deleteByKey()`, passing its own primary key.
+fn write_instance_deleter<'a>(table: &'a Table, key: &'a Key) -> String {
+    let mut buffer = String::new();
+
+    buffer += "\n\t/** Deletes this `";
+    buffer += &write_table_class_name(table);
+    buffer += "` by the primary key.\n";
+    buffer += "\t */\n";
+
+    buffer += "\tpublic function delete(): void\n";
+    buffer += "\t{\n";
+
+    buffer += "\t\tSelf::deleteByKey(";
+    for (index, column) in key.iter_columns(table).enumerate() {
+        if index > 0 {
+            buffer += ", ";
+        }
+        buffer += "$this->";
+        buffer += &write_property_name(column);
+    }
+    buffer += ");\n";
+
+    buffer += "\t}\n";
+
+    buffer
+}
+
+fn write_comment<'a>(table: &'a Table, key: &'a Key, options: &'a Options) -> String {
     let mut buffer = String::new();
 
     buffer += "\n\t/** Deletes a `";
-    buffer += &table.name.to_case(Case::Pascal);
+    buffer += &write_table_class_name(table);
     buffer += "` by the primary key.\n";
 
     for column in key.iter_columns(table) {
         buffer += "\t * @param ";
-        buffer += &write_full_comment(column);
+        buffer += &write_full_comment(column, options);
         buffer += "\n";
     }
 
@@ -42,98 +81,115 @@ fn write_comment<'a>(table: &'a Table, key: &'a Key) -> String {
     buffer
 }
 
-fn write_parameters<'a>(table: &'a Table, key: &'a Key) -> String {
+fn write_parameters<'a>(table: &'a Table, key: &'a Key, options: &'a Options) -> String {
     let mut buffer = String::new();
 
     let mut columns = key.iter_columns(table);
 
     if let Some(column) = columns.next() {
-        buffer += &write_typed_variable(column);
+        buffer += &write_typed_variable(column, options);
     }
 
     for column in columns {
         buffer += ", ";
-        buffer += &write_typed_variable(column);
+        buffer += &write_typed_variable(column, options);
     }
 
     buffer
 }
 
-fn write_body<'a>(table: &'a Table, key: &'a Key) -> String {
+fn write_body<'a>(
+    table: &'a Table,
+    key: &'a Key,
+    dialect: SQLDialect,
+    options: &'a Options,
+) -> String {
     let mut buffer = String::new();
 
-    buffer += &write_query_params(key.iter_columns(table).collect());
-
-    buffer += &write_query_prepare(table, key);
-    buffer += "\t\t$sth->execute($params);\n";
-
-    buffer
-}
+    let columns = key.iter_columns(table).collect::<Vec<_>>();
 
-fn write_query_params<'a>(columns: Vec<&'a Column>) -> String {
-    let mut buffer = String::new();
+    buffer += &write_query_params(&columns, options);
 
-    if columns.len() == 1 {
-        let column = columns[0];
-
-        buffer += "\t\t$params = array(\":";
-        buffer += &column.name;
-        buffer += "\" => $";
-        buffer += &column.name.to_case(Case::Camel);
-        buffer += ");\n";
-    } else if columns.len() > 1 {
-        buffer += "\t\t$params = array(\n";
-        for column in columns {
-            buffer += "\t\t\t\":";
-            buffer += &column.name;
-            buffer += "\" => $";
-            buffer += &column.name.to_case(Case::Camel);
-            buffer += ",\n";
-        }
-        buffer += "\t\t);\n";
-    }
+    buffer += &write_query_prepare(table, key, dialect);
+    buffer += &write_query_execute(&columns, options);
 
     buffer
 }
 
-fn write_query_prepare<'a>(table: &'a Table, key: &'a Key) -> String {
+fn write_query_prepare<'a>(table: &'a Table, key: &'a Key, dialect: SQLDialect) -> String {
     let mut buffer = String::new();
 
-    buffer += "\t\t$sth = getPDO()->prepare(\"DELETE FROM `";
-    buffer += &table.name;
-    buffer += "` WHERE ";
+    buffer += "\t\t$sth = getPDO()->prepare(\"DELETE FROM ";
+    buffer += &write_identifier(dialect, &table.name);
+    buffer += " WHERE ";
 
-    buffer += &write_query_where(key.iter_columns(table));
+    buffer += &write_query_where(key.iter_columns(table), dialect);
 
     buffer += ";\");\n";
 
     buffer
 }
 
-fn write_query_where<'a>(mut columns: impl Iterator<Item = &'a Column>) -> String {
+fn write_query_where<'a>(
+    mut columns: impl Iterator<Item = &'a Column>,
+    dialect: SQLDialect,
+) -> String {
     let mut buffer = String::new();
 
     if let Some(column) = columns.next() {
-        buffer += "`";
-        buffer += &column.name;
+        buffer += &write_identifier(dialect, &column.name);
         if column.nullable {
-            buffer += "` <=> :";
+            buffer += " ";
+            buffer += dialect.null_safe_equal();
+            buffer += " :";
         } else {
-            buffer += "` = :";
+            buffer += " = :";
         }
-        buffer += &column.name;
+        buffer += &write_parameter_name(&column.name);
     }
 
     for column in columns {
-        buffer += " AND `";
-        buffer += &column.name;
+        buffer += " AND ";
+        buffer += &write_identifier(dialect, &column.name);
         if column.nullable {
-            buffer += "` <=> :";
+            buffer += " ";
+            buffer += dialect.null_safe_equal();
+            buffer += " :";
         } else {
-            buffer += "` = :";
+            buffer += " = :";
         }
-        buffer += &column.name;
+        buffer += &write_parameter_name(&column.name);
     }
 
     buffer
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{database::dialect::SQLDialect, options::Options, parsers::parse_source};
+
+    use super::write_deleter;
+
+    #[test]
+    fn deletes_an_object_by_its_own_key() {
+        let schema = parse_source(
+            "CREATE TABLE order_lines (order_id INT NOT NULL, line INT NOT NULL, PRIMARY KEY (order_id, line));",
+            SQLDialect::MariaDB,
+        );
+        let deleter = write_deleter(
+            &schema.tables["order_lines"],
+            SQLDialect::MariaDB,
+            &Options::default(),
+        );
+
+        assert!(
+            deleter.contains("public static function deleteByKey(int $orderId, int $line): void")
+        );
+        assert!(deleter.contains(
+            "public function delete(): void
+\t{
+\t\tSelf::deleteByKey($this->orderId, $this->line);
+\t}"
+        ));
+    }
+}
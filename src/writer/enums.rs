@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+
+use crate::{
+    database::{
+        column::{Column, ColumnType},
+        schema::Schema,
+    },
+    options::Options,
+};
+
+use super::{
+    naming::{write_default_class_name, write_enum_class_name},
+    php::{write_case_names, write_string},
+};
+
+/// Names the enum of every enum and set column, after the column or its named
+/// type, like `MoodEnum` for PostgreSQL's `mood`. Columns with the same values
+/// share an enum, named after the first of them. A name used with different
+/// values, or by a `--shared-enum`, is prefixed with the class of the table,
+/// like `OrderStatusEnum`.
+pub(crate) fn name_enums<'a>(schema: &'a mut Schema, options: &'a Options) {
+    let mut table_names = schema.tables.keys().cloned().collect::<Vec<_>>();
+    table_names.sort();
+
+    // The name and first table of each set of values.
+    let mut value_sets = HashMap::new();
+
+    for table_name in &table_names {
+        for column in schema.tables[table_name].iter_columns() {
+            let Some(values) = enum_values(&column.type_) else {
+                continue;
+            };
+
+            if is_shared(options, table_name, &column.name) {
+                continue;
+            }
+
+            value_sets
+                .entry(write_value_set(values))
+                .or_insert((write_default_enum_name(column), table_name.clone()));
+        }
+    }
+
+    for table_name in &table_names {
+        let table = schema.tables.get_mut(table_name).unwrap();
+
+        for column in table.columns.values_mut() {
+            let Some(values) = enum_values(&column.type_) else {
+                continue;
+            };
+
+            if let Some(name) = options
+                .shared_enums
+                .get(&format!("{}.{}", table_name, column.name))
+            {
+                column.enum_name = Some(name.clone());
+                continue;
+            }
+
+            let (name, first_table) = &value_sets[&write_value_set(values)];
+            let is_ambiguous = value_sets
+                .values()
+                .filter(|(other_name, _)| other_name == name)
+                .count()
+                > 1
+                || options.shared_enums.values().any(|shared| shared == name);
+
+            column.enum_name = match is_ambiguous {
+                true => Some(write_default_class_name(first_table, options) + name),
+                false => Some(name.clone()),
+            };
+        }
+    }
+
+    // Every column of a shared enum gets all its values, so each value has the
+    // same case everywhere.
+    let enums = collect_enums(schema);
+    for table in schema.tables.values_mut() {
+        for column in table.columns.values_mut() {
+            let Some(name) = &column.enum_name else {
+                continue;
+            };
+            let (_, values) = enums
+                .iter()
+                .find(|(other_name, _)| other_name == name)
+                .unwrap();
+
+            if let Some(column_values) = enum_values_mut(&mut column.type_) {
+                *column_values = values.clone();
+            }
+        }
+    }
+}
+
+/// The enums to write with their values, shared enums get the values of all
+/// their columns.
+pub(crate) fn collect_enums(schema: &Schema) -> Vec<(String, Vec<String>)> {
+    let mut enums: Vec<(String, Vec<String>)> = Vec::new();
+
+    let mut table_names = schema.tables.keys().collect::<Vec<_>>();
+    table_names.sort();
+
+    for table_name in table_names {
+        for column in schema.tables[table_name].iter_columns() {
+            let (Some(name), Some(values)) = (&column.enum_name, enum_values(&column.type_)) else {
+                continue;
+            };
+
+            let index = match enums.iter().position(|(other_name, _)| other_name == name) {
+                Some(index) => index,
+                None => {
+                    enums.push((name.clone(), Vec::new()));
+                    enums.len() - 1
+                }
+            };
+
+            let enum_values = &mut enums[index].1;
+            for value in values {
+                if !enum_values.contains(value) {
+                    enum_values.push(value.clone());
+                }
+            }
+        }
+    }
+
+    enums
+}
+
+/// The values of an enum regardless of their order.
+fn write_value_set(values: &[String]) -> Vec<String> {
+    let mut values = values.to_vec();
+    values.sort();
+
+    values
+}
+
+fn is_shared<'a>(options: &'a Options, table_name: &'a str, column_name: &'a str) -> bool {
+    options
+        .shared_enums
+        .contains_key(&format!("{}.{}", table_name, column_name))
+}
+
+/// The enum of a column before naming, after the named type of the column or
+/// else the column.
+fn write_default_enum_name(column: &Column) -> String {
+    match enum_type_name(&column.type_) {
+        // Types can be qualified by their schema.
+        Some(type_name) => write_enum_class_name(type_name.rsplit('.').next().unwrap()),
+        None => write_enum_class_name(&column.name),
+    }
+}
+
+fn enum_type_name(type_: &ColumnType) -> Option<&String> {
+    match type_ {
+        ColumnType::Enum(_, type_name) => type_name.as_ref(),
+        ColumnType::Array(element) => enum_type_name(element),
+        _ => None,
+    }
+}
+
+/// The values of an enum, set or array of enums.
+pub(crate) fn enum_values(type_: &ColumnType) -> Option<&Vec<String>> {
+    match type_ {
+        ColumnType::Enum(values, _) | ColumnType::Set(values) => Some(values),
+        ColumnType::Array(element) => enum_values(element),
+        _ => None,
+    }
+}
+
+fn enum_values_mut(type_: &mut ColumnType) -> Option<&mut Vec<String>> {
+    match type_ {
+        ColumnType::Enum(values, _) | ColumnType::Set(values) => Some(values),
+        ColumnType::Array(element) => enum_values_mut(element),
+        _ => None,
+    }
+}
+
+pub(crate) fn write_enum<'a>(name: &'a str, values: &'a [String]) -> String {
+    let mut buffer = String::new();
+
+    buffer += "enum ";
+    buffer += name;
+    buffer += ": string\n";
+    buffer += "{\n";
+
+    for (value, case_name) in values.iter().zip(write_case_names(values)) {
+        buffer += "\tcase ";
+        buffer += &case_name;
+        buffer += " = ";
+        buffer += &write_string(value);
+        buffer += ";\n";
+    }
+
+    buffer += "}\n";
+
+    buffer
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{database::dialect::SQLDialect, options::Options, parsers::parse_source};
+
+    use super::{collect_enums, name_enums};
+
+    #[test]
+    fn names_enum_types_after_the_type() {
+        let mut schema = parse_source(
+            "CREATE TYPE mood AS ENUM ('sad', 'happy');
+            CREATE TABLE people (feeling mood, current_mood mood);",
+            SQLDialect::PostgreSQL,
+        );
+        name_enums(&mut schema, &Options::default());
+
+        let enums = collect_enums(&schema);
+        assert_eq!(enums.len(), 1);
+        assert_eq!(enums[0].0, "MoodEnum");
+    }
+
+    #[test]
+    fn shares_enums_by_values() {
+        let mut schema = parse_source(
+            "CREATE TABLE orders (status ENUM('open', 'closed'), kind ENUM('a', 'b'));
+            CREATE TABLE tickets (state ENUM('closed', 'open'), kind ENUM('c', 'd'));",
+            SQLDialect::MariaDB,
+        );
+        name_enums(&mut schema, &Options::default());
+
+        let enum_name = |table_name: &str, column_name: &str| {
+            schema.tables[table_name].columns[column_name]
+                .enum_name
+                .clone()
+                .unwrap()
+        };
+        assert_eq!(enum_name("orders", "status"), "StatusEnum");
+        assert_eq!(enum_name("tickets", "state"), "StatusEnum");
+        assert_eq!(enum_name("orders", "kind"), "OrderKindEnum");
+        assert_eq!(enum_name("tickets", "kind"), "TicketKindEnum");
+    }
+
+    #[test]
+    fn names_shared_enums_by_option() {
+        let mut schema = parse_source(
+            "CREATE TABLE orders (status ENUM('open', 'closed'));
+            CREATE TABLE tickets (state ENUM('open', 'closed', 'stale'));",
+            SQLDialect::MariaDB,
+        );
+        let mut options = Options::default();
+        for column_name in ["orders.status", "tickets.state"] {
+            options
+                .shared_enums
+                .insert(column_name.to_string(), "StateEnum".to_string());
+        }
+        name_enums(&mut schema, &options);
+
+        let enums = collect_enums(&schema);
+        assert_eq!(enums.len(), 1);
+        assert_eq!(enums[0].0, "StateEnum");
+        assert_eq!(enums[0].1, ["open", "closed", "stale"]);
+    }
+
+    #[test]
+    fn prefixes_names_taken_by_shared_enums() {
+        let mut schema = parse_source(
+            "CREATE TABLE orders (status ENUM('open', 'closed'));
+            CREATE TABLE tickets (state ENUM('new', 'done'));",
+            SQLDialect::MariaDB,
+        );
+        let mut options = Options::default();
+        options
+            .shared_enums
+            .insert("tickets.state".to_string(), "StatusEnum".to_string());
+        name_enums(&mut schema, &options);
+
+        let enums = collect_enums(&schema);
+        assert_eq!(enums.len(), 2);
+        assert_eq!(
+            schema.tables["orders"].columns["status"]
+                .enum_name
+                .as_deref(),
+            Some("OrderStatusEnum")
+        );
+        assert_eq!(
+            schema.tables["tickets"].columns["state"]
+                .enum_name
+                .as_deref(),
+            Some("StatusEnum")
+        );
+    }
+}
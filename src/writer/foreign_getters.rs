@@ -1,70 +1,177 @@
 use convert_case::{Case, Casing};
 
-use crate::database::{keys::ForeignKey, table::Table};
+use crate::{
+    database::{column::Column, keys::ForeignKey, schema::Schema, table::Table},
+    options::Options,
+};
+
+use super::{
+    getters::write_getter_name,
+    naming::{write_default_class_name, write_property_name, write_table_class_name},
+    references::find_key,
+    trees::is_tree,
+};
 
 pub(crate) fn write_foreign_getters<'a>(
     table: &'a Table,
     foreign_keys: impl Iterator<Item = &'a ForeignKey>,
+    schema: &'a Schema,
+    options: &'a Options,
 ) -> String {
     let mut buffer = String::new();
 
     for foreign_key in foreign_keys {
-        let nullable_column_names = foreign_key
-            .iter_column_names()
-            .filter(|column_name| table.columns[*column_name].nullable)
+        let foreign_class_name = write_foreign_class_name(foreign_key, schema, options);
+
+        let nullable_columns = foreign_key
+            .iter_columns(table)
+            .filter(|column| column.nullable)
             .collect::<Vec<_>>();
 
-        buffer += &write_comment(table, foreign_key);
+        buffer += &write_comment(table, foreign_key, &foreign_class_name);
 
-        buffer += "\tpublic function get";
-        buffer += &foreign_key.name.to_case(Case::Pascal);
+        buffer += "\tpublic function ";
+        buffer += &write_foreign_getter_name(foreign_key, schema, options);
 
-        if !nullable_column_names.is_empty() {
+        if !nullable_columns.is_empty() {
             buffer += "(): false|";
         } else {
             buffer += "(): ";
         }
 
-        buffer += &foreign_key.foreign_table_name.to_case(Case::Pascal);
+        buffer += &foreign_class_name;
 
         buffer += "\n\t{\n";
 
-        if !nullable_column_names.is_empty() {
+        if !nullable_columns.is_empty() {
             buffer += "\t\tif (!isset(";
 
-            buffer += &write_arguments(nullable_column_names.into_iter());
+            buffer += &write_arguments(nullable_columns.into_iter());
 
             buffer += "))\n";
             buffer += "\t\t\treturn false;\n\n";
         }
 
         buffer += "\t\treturn ";
-        buffer += &foreign_key.foreign_table_name.to_case(Case::Pascal);
-        buffer += "::get(";
-        buffer += &write_arguments(foreign_key.iter_column_names());
-        buffer += ");\n\t}\n";
+        buffer += &foreign_class_name;
+        buffer += "::";
+        buffer += &write_foreign_call(table, foreign_key, schema);
+        buffer += ";\n\t}\n";
+    }
+
+    buffer
+}
+
+/// The name of the getter of a foreign key, after its column without the
+/// `_id`, like `getCustomer` for `customer_id`. Foreign keys with several
+/// columns are named after the class they reference, and the only one
+/// referencing its own table is `getParent`.
+pub(crate) fn write_foreign_getter_name<'a>(
+    foreign_key: &'a ForeignKey,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
+    if let Some(name) = &foreign_key.method_name {
+        return name.clone();
+    }
+
+    if is_tree(foreign_key, schema) {
+        return "getParent".to_string();
     }
 
+    "get".to_string() + &write_foreign_name(foreign_key, schema, options)
+}
+
+/// The name of what a foreign key references, like `Customer` for
+/// `customer_id`, or the class it references.
+pub(crate) fn write_foreign_name<'a>(
+    foreign_key: &'a ForeignKey,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
+    let name = match foreign_key.column_names.as_slice() {
+        [column_name] => column_name
+            .to_case(Case::Snake)
+            .strip_suffix("_id")
+            .filter(|name| !name.is_empty())
+            .map(|name| name.to_case(Case::Pascal)),
+        _ => None,
+    };
+
+    name.unwrap_or_else(|| write_foreign_class_name(foreign_key, schema, options))
+}
+
+pub(crate) fn write_foreign_class_name<'a>(
+    foreign_key: &'a ForeignKey,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
+    match schema.tables.get(&foreign_key.foreign_table_name) {
+        Some(foreign_table) => write_table_class_name(foreign_table),
+        None => write_default_class_name(&foreign_key.foreign_table_name, options),
+    }
+}
+
+/// Calls the getter of the key the foreign key references, `get()` for the
+/// primary key.
+fn write_foreign_call<'a>(
+    table: &'a Table,
+    foreign_key: &'a ForeignKey,
+    schema: &'a Schema,
+) -> String {
+    let mut buffer = String::new();
+
+    let key = schema
+        .tables
+        .get(&foreign_key.foreign_table_name)
+        .and_then(|foreign_table| find_key(foreign_table, &foreign_key.foreign_column_names));
+
+    let Some(key) = key else {
+        buffer += "get(";
+        buffer += &write_arguments(foreign_key.iter_columns(table));
+        buffer += ")";
+        return buffer;
+    };
+
+    // The key can have the columns in another order than the foreign key.
+    let columns = key.iter_column_names().map(|foreign_column_name| {
+        let position = foreign_key
+            .iter_foreign_column_names()
+            .position(|other| other == foreign_column_name)
+            .unwrap();
+
+        &table.columns[&foreign_key.column_names[position]]
+    });
+
+    buffer += &write_getter_name(key);
+    buffer += "(";
+    buffer += &write_arguments(columns);
+    buffer += ")";
+
     buffer
 }
 
-fn write_arguments<'a>(mut arguments: impl Iterator<Item = &'a String>) -> String {
+fn write_arguments<'a>(mut columns: impl Iterator<Item = &'a Column>) -> String {
     let mut buffer = String::new();
 
-    if let Some(argument) = arguments.next() {
+    if let Some(column) = columns.next() {
         buffer += "$this->";
-        buffer += argument;
+        buffer += &write_property_name(column);
     }
 
-    for argument in arguments {
+    for column in columns {
         buffer += ", $this->";
-        buffer += argument;
+        buffer += &write_property_name(column);
     }
 
     buffer
 }
 
-fn write_comment<'a>(table: &'a Table, foreign_key: &'a ForeignKey) -> String {
+fn write_comment<'a>(
+    table: &'a Table,
+    foreign_key: &'a ForeignKey,
+    foreign_class_name: &'a str,
+) -> String {
     let mut buffer = String::new();
 
     let nullable = foreign_key
@@ -72,7 +179,7 @@ fn write_comment<'a>(table: &'a Table, foreign_key: &'a ForeignKey) -> String {
         .any(|column| column.nullable);
 
     buffer += "\n\t/** Gets the associated `";
-    buffer += &table.name;
+    buffer += foreign_class_name;
     buffer += "` by the `";
     buffer += &foreign_key.name;
     buffer += "` key.\n";
@@ -81,7 +188,7 @@ fn write_comment<'a>(table: &'a Table, foreign_key: &'a ForeignKey) -> String {
     if nullable {
         buffer += "false|";
     }
-    buffer += &table.name.to_case(Case::Pascal);
+    buffer += foreign_class_name;
     buffer += " The corresponding object";
 
     if nullable {
@@ -93,3 +200,120 @@ fn write_comment<'a>(table: &'a Table, foreign_key: &'a ForeignKey) -> String {
 
     buffer
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        database::{
+            column::{Column, ColumnType},
+            dialect::SQLDialect,
+            keys::{ForeignKey, Key},
+            schema::Schema,
+            table::Table,
+        },
+        options::Options,
+    };
+
+    use super::{write_foreign_getter_name, write_foreign_getters};
+
+    fn create_table(name: &str, columns: &[(&str, bool)]) -> Table {
+        let mut table = Table::new(name.into(), Vec::new(), HashMap::new());
+
+        for (column_name, nullable) in columns {
+            let column = Column::new(
+                column_name.to_string(),
+                ColumnType::Int,
+                *nullable,
+                false,
+                "".into(),
+                None,
+            );
+
+            table.column_names.push(column_name.to_string());
+            table.columns.insert(column_name.to_string(), column);
+        }
+
+        table
+    }
+
+    fn create_foreign_key(table_name: &str, column_names: &[&str], foreign: &str) -> ForeignKey {
+        let (foreign_table_name, foreign_column_names) = foreign.split_once('.').unwrap();
+
+        ForeignKey::new(
+            format!("fk_{}_{}", table_name, foreign_table_name),
+            table_name.into(),
+            column_names.iter().map(|name| name.to_string()).collect(),
+            foreign_table_name.into(),
+            foreign_column_names.split(',').map(String::from).collect(),
+        )
+    }
+
+    #[test]
+    fn names_getters_after_the_column() {
+        let mut schema = Schema::new(SQLDialect::MariaDB);
+        let mut users = create_table("users", &[("id", false)]);
+        users.class_name = Some("User".into());
+        schema.tables.insert("users".into(), users);
+
+        let options = Options::default();
+
+        let customer = create_foreign_key("orders", &["customerId"], "users.id");
+        let owner = create_foreign_key("orders", &["owner"], "users.id");
+        let shop_customer = create_foreign_key("orders", &["shop_id", "user_id"], "users.id");
+
+        assert_eq!(
+            write_foreign_getter_name(&customer, &schema, &options),
+            "getCustomer"
+        );
+        assert_eq!(
+            write_foreign_getter_name(&owner, &schema, &options),
+            "getUser"
+        );
+        assert_eq!(
+            write_foreign_getter_name(&shop_customer, &schema, &options),
+            "getUser"
+        );
+    }
+
+    #[test]
+    fn calls_the_getter_of_the_referenced_unique_key() {
+        let mut schema = Schema::new(SQLDialect::MariaDB);
+
+        let mut products = create_table(
+            "products",
+            &[("id", false), ("shop_id", false), ("sku", false)],
+        );
+        let key = Key::new(
+            "sku_shop".into(),
+            true,
+            "products".into(),
+            vec!["sku".into(), "shop_id".into()],
+        );
+        products.class_name = Some("Product".into());
+        products.key_names.push(key.name.clone());
+        products.keys.insert(key.name.clone(), key);
+        schema.tables.insert("products".into(), products);
+
+        let lines = create_table("order_lines", &[("shop_id", false), ("product_sku", true)]);
+        let foreign_key = create_foreign_key(
+            "order_lines",
+            &["shop_id", "product_sku"],
+            "products.shop_id,sku",
+        );
+
+        assert_eq!(
+            write_foreign_getters(&lines, [&foreign_key].into_iter(), &schema, &Options::default()),
+            "\n\t/** Gets the associated `Product` by the `fk_order_lines_products` key.\n\
+            \t * @return false|Product The corresponding object, `false` when one of the keys columns equal `null`.\n\
+            \t */\n\
+            \tpublic function getProduct(): false|Product\n\
+            \t{\n\
+            \t\tif (!isset($this->productSku))\n\
+            \t\t\treturn false;\n\n\
+            \t\treturn Product::getBySkuShop($this->productSku, $this->shopId);\n\
+            \t}\n"
+        );
+    }
+}
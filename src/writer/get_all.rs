@@ -1,11 +1,19 @@
-use convert_case::{Case, Casing};
+use crate::{
+    database::{dialect::SQLDialect, table::Table},
+    options::Options,
+};
 
-use crate::database::{
-    column::{Column, ColumnType},
-    table::Table,
+use super::{
+    column::write_row_value,
+    naming::{write_table_class_name, write_table_variable},
+    sql::write_identifier,
 };
 
-pub(crate) fn write_getter<'a>(table: &'a Table) -> String {
+pub(crate) fn write_getter<'a>(
+    table: &'a Table,
+    dialect: SQLDialect,
+    options: &'a Options,
+) -> String {
     let mut buffer = String::new();
 
     buffer += &write_comment(table);
@@ -13,14 +21,14 @@ pub(crate) fn write_getter<'a>(table: &'a Table) -> String {
     buffer += "\tpublic static function getAll(): array\n";
     buffer += "\t{\n";
 
-    buffer += &write_body(table);
+    buffer += &write_body(table, dialect, options);
 
     buffer += "\t}\n";
 
     buffer
 }
 
-fn write_comment<'a>(table: &'a Table) -> String {
+fn write_comment(table: &Table) -> String {
     let mut buffer = String::new();
 
     buffer += "\n\t/** ";
@@ -38,74 +46,54 @@ fn write_comment<'a>(table: &'a Table) -> String {
     buffer
 }
 
-fn write_body<'a>(table: &'a Table) -> String {
+fn write_body<'a>(table: &'a Table, dialect: SQLDialect, options: &'a Options) -> String {
     let mut buffer = String::new();
 
-    buffer += &write_query_prepare(table);
+    buffer += &write_query_prepare(table, dialect);
     buffer += "\t\t$sth->execute();\n\n";
 
     buffer += "\t\t$";
-    buffer += &table.name.to_case(Case::Camel);
+    buffer += &write_table_variable(&table.name);
     buffer += " = array();\n\n";
     buffer += "\t\twhile ($row = $sth->fetch())\n";
 
     buffer += "\t\t\t$";
-    buffer += &table.name.to_case(Case::Camel);
+    buffer += &write_table_variable(&table.name);
     buffer += "[] = new ";
-    buffer += &table.name.to_case(Case::Pascal);
+    buffer += &write_table_class_name(table);
     buffer += "(";
-    buffer += &write_object_arguments(table);
+    buffer += &write_object_arguments(table, options);
     buffer += ");\n\n";
 
     buffer += "\t\treturn $";
-    buffer += &table.name.to_case(Case::Camel);
+    buffer += &write_table_variable(&table.name);
     buffer += ";\n";
 
     buffer
 }
 
-fn write_query_prepare<'a>(table: &'a Table) -> String {
+fn write_query_prepare(table: &Table, dialect: SQLDialect) -> String {
     let mut buffer = String::new();
 
-    buffer += "\t\t$sth = getPDO()->prepare(\"SELECT * FROM `";
-    buffer += &table.name;
-    buffer += "`;\");\n";
+    buffer += "\t\t$sth = getPDO()->prepare(\"SELECT * FROM ";
+    buffer += &write_identifier(dialect, &table.name);
+    buffer += ";\");\n";
 
     buffer
 }
 
-fn write_object_arguments<'a>(table: &'a Table) -> String {
+fn write_object_arguments<'a>(table: &'a Table, options: &'a Options) -> String {
     let mut buffer = String::new();
 
     let mut columns = table.iter_columns();
 
     if let Some(column) = columns.next() {
-        buffer += &write_object_argument(column);
+        buffer += &write_row_value(column, options);
     }
 
     for column in columns {
         buffer += ", ";
-        buffer += &write_object_argument(column);
-    }
-
-    buffer
-}
-
-fn write_object_argument<'a>(column: &'a Column) -> String {
-    let mut buffer = String::new();
-
-    match column.type_ {
-        ColumnType::Enum(_) => {
-            buffer += &column.name.to_case(Case::Pascal);
-            buffer += "Enum::from($row[\"";
-            buffer += &column.name;
-            buffer += "\"])";
-        }
-        _ => {
-            buffer += "$row[\"";
-            buffer += &column.name;
-            buffer += "\"]";
-        }
+        buffer += &write_row_value(column, options);
     }
 
     buffer
@@ -1,82 +1,200 @@
 use convert_case::{Case, Casing};
 
-use crate::database::{
-    column::{Column, ColumnType},
-    keys::Key,
-    table::Table,
+use crate::{
+    database::{column::Column, dialect::SQLDialect, keys::Key, table::Table},
+    options::Options,
 };
 
 use super::{
-    column::{write_full_comment, write_typed_variable, write_variable},
+    column::{write_full_comment, write_row_value, write_typed_variable, write_variable},
     get_all,
+    naming::{write_table_class_name, write_table_variable},
+    params::{write_query_execute, write_query_params},
+    sql::{write_identifier, write_parameter_name},
 };
 
-pub(crate) fn write_getters<'a>(table: &'a Table) -> String {
+pub(crate) fn write_getters<'a>(
+    table: &'a Table,
+    dialect: SQLDialect,
+    options: &'a Options,
+) -> String {
     let mut buffer = String::new();
 
     if let Some(primary_key) = &table.primary_key {
-        buffer += &write_comment(table, primary_key);
-        buffer += &write_getter(table, primary_key);
+        buffer += &write_comment(table, primary_key, options);
+        buffer += &write_getter(table, primary_key, dialect, options);
     }
 
-    buffer += &get_all::write_getter(table);
+    buffer += &get_all::write_getter(table, dialect, options);
+
+    for key in table.iter_keys() {
+        buffer += &write_comment(table, key, options);
+        buffer += &write_getter(table, key, dialect, options);
+    }
 
     for key in table.iter_keys() {
-        buffer += &write_comment(table, key);
-        buffer += &write_getter(table, key);
+        buffer += &write_json_path_getter(table, key, options);
     }
 
     buffer
 }
 
-fn write_getter<'a>(table: &'a Table, key: &'a Key) -> String {
+fn write_getter<'a>(
+    table: &'a Table,
+    key: &'a Key,
+    dialect: SQLDialect,
+    options: &'a Options,
+) -> String {
     let mut buffer = String::new();
 
-    buffer += "\tpublic static function get";
-    if !key.name.is_empty() {
-        buffer += "By";
-    }
-    buffer += &key.name.to_case(Case::Pascal);
+    buffer += "\tpublic static function ";
+    buffer += &write_getter_name(key);
     buffer += "(";
 
     buffer += &write_parameters(
         key.iter_column_names()
             .map(|column_name| &table.columns[column_name]),
+        options,
     );
 
     if key.unique {
         buffer += "): null|";
-        buffer += &table.name.to_case(Case::Pascal);
+        buffer += &write_table_class_name(table);
     } else {
         buffer += "): array";
     }
     buffer += "\n\t{\n";
 
-    buffer += &write_body(table, key);
+    buffer += &write_body(table, key, dialect, options);
 
     buffer += "\t}\n";
 
     buffer
 }
 
-fn write_comment<'a>(table: &'a Table, key: &'a Key) -> String {
+/// The name of the getter of a key, `get` for the primary key.
+pub(crate) fn write_getter_name(key: &Key) -> String {
+    if let Some(name) = &key.method_name {
+        return name.clone();
+    }
+
+    let mut buffer = String::new();
+
+    buffer += "get";
+    if !key.name.is_empty() {
+        buffer += "By";
+    }
+    buffer += &key.name.to_case(Case::Pascal);
+
+    buffer
+}
+
+/// The name of the getter by the JSON path of a key, like
+/// `getByDataAddressCityJsonPath`, `None` when the key isn't on a JSON path.
+pub(crate) fn write_json_path_getter_name(table: &Table, key: &Key) -> Option<String> {
+    if let Some(name) = &key.json_path_method_name {
+        return Some(name.clone());
+    }
+
+    let [column_name] = key.column_names.as_slice() else {
+        return None;
+    };
+    let json_path = table.columns[column_name].json_path.as_ref()?;
+
+    let mut buffer = String::new();
+
+    buffer += "getBy";
+    buffer += &json_path.column_name.to_case(Case::Pascal);
+    buffer += &json_path
+        .path
+        .replace(|c: char| !c.is_alphanumeric(), " ")
+        .to_case(Case::Pascal);
+    buffer += "JsonPath";
+
+    Some(buffer)
+}
+
+/// Gets rows by a path in a JSON column, through the key on the generated
+/// column that extracts it.
+fn write_json_path_getter<'a>(table: &'a Table, key: &'a Key, options: &'a Options) -> String {
+    let mut buffer = String::new();
+
+    let [column_name] = key.column_names.as_slice() else {
+        return buffer;
+    };
+    let column = &table.columns[column_name];
+    let Some(json_path) = &column.json_path else {
+        return buffer;
+    };
+
+    buffer += "\n\t/** ";
+    if key.unique {
+        buffer += "Gets a `";
+    } else {
+        buffer += "Gets several `";
+    }
+    buffer += &write_table_class_name(table);
+    buffer += "` by the `";
+    buffer += &json_path.path;
+    buffer += "` path of `";
+    buffer += &json_path.column_name;
+    buffer += "`.\n";
+
+    buffer += "\t * @param ";
+    buffer += &write_full_comment(column, options);
+    buffer += "\n";
+
+    if key.unique {
+        buffer += "\t * @return null|";
+        buffer += &write_table_class_name(table);
+        buffer += " The corresponding object, `null` when the row doesn't exist.\n";
+    } else {
+        buffer += "\t * @return array zero or more corresponding objects.\n";
+    }
+    buffer += "\t */\n";
+
+    buffer += "\tpublic static function ";
+    buffer += &write_json_path_getter_name(table, key).unwrap();
+    buffer += "(";
+    buffer += &write_typed_variable(column, options);
+
+    if key.unique {
+        buffer += "): null|";
+        buffer += &write_table_class_name(table);
+    } else {
+        buffer += "): array";
+    }
+    buffer += "\n\t{\n";
+
+    buffer += "\t\treturn Self::";
+    buffer += &write_getter_name(key);
+    buffer += "(";
+    buffer += &write_variable(column);
+    buffer += ");\n";
+
+    buffer += "\t}\n";
+
+    buffer
+}
+
+fn write_comment<'a>(table: &'a Table, key: &'a Key, options: &'a Options) -> String {
     let mut buffer = String::new();
 
     buffer += "\n\t/** ";
 
     if key.name.is_empty() {
         buffer += "Gets a `";
-        buffer += &table.name.to_case(Case::Pascal);
+        buffer += &write_table_class_name(table);
         buffer += "` by the primary key.\n";
     } else if key.unique {
         buffer += "Gets a `";
-        buffer += &table.name.to_case(Case::Pascal);
+        buffer += &write_table_class_name(table);
         buffer += "` by the `";
         buffer += &key.name;
         buffer += "` key.\n";
     } else {
         buffer += "Gets several `";
-        buffer += &table.name.to_case(Case::Pascal);
+        buffer += &write_table_class_name(table);
         buffer += "` by the `";
         buffer += &key.name;
         buffer += "` key.\n";
@@ -84,13 +202,13 @@ fn write_comment<'a>(table: &'a Table, key: &'a Key) -> String {
 
     for column in key.iter_column_names() {
         buffer += "\t * @param ";
-        buffer += &write_full_comment(&table.columns[column]);
+        buffer += &write_full_comment(&table.columns[column], options);
         buffer += "\n";
     }
 
     if key.unique {
         buffer += "\t * @return null|";
-        buffer += &table.name.to_case(Case::Pascal);
+        buffer += &write_table_class_name(table);
         buffer += " The corresponding object";
 
         buffer += ", `null` when the row doesn't exist.\n";
@@ -103,99 +221,84 @@ fn write_comment<'a>(table: &'a Table, key: &'a Key) -> String {
     buffer
 }
 
-fn write_parameters<'a>(mut columns: impl Iterator<Item = &'a Column>) -> String {
+fn write_parameters<'a>(
+    mut columns: impl Iterator<Item = &'a Column>,
+    options: &'a Options,
+) -> String {
     let mut buffer = String::new();
 
     if let Some(column) = columns.next() {
-        buffer += &write_typed_variable(column);
+        buffer += &write_typed_variable(column, options);
     }
 
     for column in columns {
         buffer += ", ";
-        buffer += &write_typed_variable(column);
+        buffer += &write_typed_variable(column, options);
     }
 
     buffer
 }
 
-fn write_body<'a>(table: &'a Table, key: &'a Key) -> String {
+fn write_body<'a>(
+    table: &'a Table,
+    key: &'a Key,
+    dialect: SQLDialect,
+    options: &'a Options,
+) -> String {
     let mut buffer = String::new();
 
-    buffer += &write_query_params(&key.column_names);
+    let columns = key.iter_columns(table).collect::<Vec<_>>();
 
-    buffer += &write_query_prepare(table, key);
-    buffer += "\t\t$sth->execute($params);\n\n";
+    buffer += &write_query_params(&columns, options);
+
+    buffer += &write_query_prepare(table, key, dialect);
+    buffer += &write_query_execute(&columns, options);
+    buffer += "\n";
 
     if key.unique {
         buffer += "\t\tif ($row = $sth->fetch())\n";
 
         buffer += "\t\t\treturn new ";
-        buffer += &table.name.to_case(Case::Pascal);
+        buffer += &write_table_class_name(table);
         buffer += "(";
-        buffer += &write_object_arguments(table.iter_columns(), &key.column_names);
+        buffer += &write_object_arguments(table.iter_columns(), &key.column_names, options);
         buffer += ");\n\n";
 
         buffer += "\t\treturn null;\n";
     } else {
         buffer += "\t\t$";
-        buffer += &table.name.to_case(Case::Camel);
+        buffer += &write_table_variable(&table.name);
         buffer += " = array();\n\n";
         buffer += "\t\twhile ($row = $sth->fetch())\n";
 
         buffer += "\t\t\t$";
-        buffer += &table.name.to_case(Case::Camel);
+        buffer += &write_table_variable(&table.name);
         buffer += "[] = new ";
-        buffer += &table.name.to_case(Case::Pascal);
+        buffer += &write_table_class_name(table);
         buffer += "(";
-        buffer += &write_object_arguments(table.iter_columns(), &key.column_names);
+        buffer += &write_object_arguments(table.iter_columns(), &key.column_names, options);
         buffer += ");\n\n";
 
         buffer += "\t\treturn $";
-        buffer += &table.name.to_case(Case::Camel);
+        buffer += &write_table_variable(&table.name);
         buffer += ";\n";
     }
 
     buffer
 }
 
-fn write_query_params<'a>(column_names: &'a Vec<String>) -> String {
-    let mut buffer = String::new();
-
-    if column_names.len() == 1 {
-        let column_name = &column_names[0];
-
-        buffer += "\t\t$params = array(\":";
-        buffer += column_name;
-        buffer += "\" => $";
-        buffer += &column_name.to_case(Case::Camel);
-        buffer += ");\n";
-    } else if column_names.len() > 1 {
-        buffer += "\t\t$params = array(\n";
-        for column_name in column_names {
-            buffer += "\t\t\t\":";
-            buffer += column_name;
-            buffer += "\" => $";
-            buffer += &column_name.to_case(Case::Camel);
-            buffer += ",\n";
-        }
-        buffer += "\t\t);\n";
-    }
-
-    buffer
-}
-
-fn write_query_prepare<'a>(table: &'a Table, key: &'a Key) -> String {
+fn write_query_prepare<'a>(table: &'a Table, key: &'a Key, dialect: SQLDialect) -> String {
     let mut buffer = String::new();
 
     buffer += "\t\t$sth = getPDO()->prepare(\"SELECT ";
 
-    buffer += &write_query_select(table.iter_column_names(), &key.column_names);
+    buffer += &write_query_select(table.iter_column_names(), &key.column_names, dialect);
 
-    buffer += " FROM `";
-    buffer += &table.name;
-    buffer += "` WHERE ";
+    buffer += " FROM ";
+    buffer += &write_identifier(dialect, &table.name);
+    buffer += " WHERE ";
 
-    buffer += &write_query_where(key.iter_column_names());
+    buffer += &write_query_where(key.iter_column_names(), dialect);
 
     if key.unique {
         buffer += " LIMIT 1;\");\n";
@@ -208,43 +311,49 @@ fn write_query_prepare<'a>(table: &'a Table, key: &'a Key) -> String {
 
 fn write_query_select<'a>(
     column_names: impl Iterator<Item = &'a String>,
-    parameter_column_names: &'a Vec<String>,
+    parameter_column_names: &'a [String],
+    dialect: SQLDialect,
 ) -> String {
     let mut buffer = String::new();
 
     let mut column_names =
         column_names.filter(|column_name| !parameter_column_names.contains(column_name));
 
-    if let Some(column_name) = column_names.next() {
-        buffer += "`";
-        buffer += column_name;
-        buffer += "`";
+    match column_names.next() {
+        Some(column_name) => buffer += &write_identifier(dialect, column_name),
+        // Every column is a parameter, only the existence of the row matters.
+        None => buffer += "1",
     }
 
     for column_name in column_names {
-        buffer += ", `";
-        buffer += column_name;
-        buffer += "`";
+        buffer += ", ";
+        buffer += &write_identifier(dialect, column_name);
     }
 
     buffer
 }
 
-fn write_query_where<'a>(mut column_names: impl Iterator<Item = &'a String>) -> String {
+fn write_query_where<'a>(
+    mut column_names: impl Iterator<Item = &'a String>,
+    dialect: SQLDialect,
+) -> String {
     let mut buffer = String::new();
 
     if let Some(column_name) = column_names.next() {
-        buffer += "`";
-        buffer += column_name;
-        buffer += "` <=> :";
-        buffer += column_name;
+        buffer += &write_identifier(dialect, column_name);
+        buffer += " ";
+        buffer += dialect.null_safe_equal();
+        buffer += " :";
+        buffer += &write_parameter_name(column_name);
     }
 
     for column_name in column_names {
-        buffer += " AND `";
-        buffer += column_name;
-        buffer += "` <=> :";
-        buffer += column_name;
+        buffer += " AND ";
+        buffer += &write_identifier(dialect, column_name);
+        buffer += " ";
+        buffer += dialect.null_safe_equal();
+        buffer += " :";
+        buffer += &write_parameter_name(column_name);
     }
 
     buffer
@@ -252,17 +361,18 @@ fn write_query_where<'a>(mut column_names: impl Iterator<Item = &'a String>) ->
 
 fn write_object_arguments<'a>(
     mut columns: impl Iterator<Item = &'a Column>,
-    parameter_column_names: &'a Vec<String>,
+    parameter_column_names: &'a [String],
+    options: &'a Options,
 ) -> String {
     let mut buffer = String::new();
 
     if let Some(column) = columns.next() {
-        buffer += &write_object_argument(column, parameter_column_names);
+        buffer += &write_object_argument(column, parameter_column_names, options);
     }
 
     for column in columns {
         buffer += ", ";
-        buffer += &write_object_argument(column, parameter_column_names);
+        buffer += &write_object_argument(column, parameter_column_names, options);
     }
 
     buffer
@@ -270,27 +380,12 @@ fn write_object_arguments<'a>(
 
 fn write_object_argument<'a>(
     column: &'a Column,
-    parameter_column_names: &'a Vec<String>,
+    parameter_column_names: &'a [String],
+    options: &'a Options,
 ) -> String {
-    let mut buffer = String::new();
-
     if parameter_column_names.contains(&column.name) {
-        buffer += &write_variable(&column);
+        write_variable(column)
     } else {
-        match column.type_ {
-            ColumnType::Enum(_) => {
-                buffer += &column.name.to_case(Case::Pascal);
-                buffer += "Enum::from($row[\"";
-                buffer += &column.name;
-                buffer += "\"])";
-            }
-            _ => {
-                buffer += "$row[\"";
-                buffer += &column.name;
-                buffer += "\"]";
-            }
-        }
+        write_row_value(column, options)
     }
-
-    buffer
 }
@@ -0,0 +1,219 @@
+use crate::options::Options;
+
+/// Words that are the same in singular and plural.
+const UNCOUNTABLE: &[&str] = &[
+    "data",
+    "equipment",
+    "feedback",
+    "fish",
+    "information",
+    "media",
+    "metadata",
+    "news",
+    "series",
+    "sheep",
+    "species",
+];
+
+/// Singulars ending in `e` whose plural would lose it, like `movie` that
+/// would become `movy`.
+const E_SINGULARS: &[&str] = &[
+    "avalanche",
+    "brownie",
+    "calorie",
+    "cliche",
+    "cookie",
+    "genie",
+    "movie",
+    "niche",
+    "prairie",
+    "quiche",
+    "rookie",
+    "selfie",
+    "zombie",
+];
+
+/// Singulars and plurals that don't follow the rules.
+const IRREGULAR: &[(&str, &str)] = &[
+    ("analysis", "analyses"),
+    ("child", "children"),
+    ("criterion", "criteria"),
+    ("foot", "feet"),
+    ("goose", "geese"),
+    ("half", "halves"),
+    ("knife", "knives"),
+    ("leaf", "leaves"),
+    ("life", "lives"),
+    ("man", "men"),
+    ("mouse", "mice"),
+    ("ox", "oxen"),
+    ("person", "people"),
+    ("shelf", "shelves"),
+    ("tooth", "teeth"),
+    ("wife", "wives"),
+    ("woman", "women"),
+];
+
+/// Makes the last word of a PascalCase name singular, like `OrderItems` to `OrderItem`.
+pub(crate) fn singularize<'a>(name: &'a str, options: &'a Options) -> String {
+    let (start, word) = split_last_word(name);
+    let lowercase = word.to_lowercase();
+
+    let singular = match options.singulars.get(&lowercase) {
+        Some(singular) => singular.clone(),
+        None => match IRREGULAR.iter().find(|(_, plural)| *plural == lowercase) {
+            Some((singular, _)) => singular.to_string(),
+            None => singularize_word(&lowercase),
+        },
+    };
+
+    start.to_string() + &write_capitalized(word, &singular)
+}
+
+/// Makes the last word of a PascalCase name plural, like `OrderItem` to `OrderItems`.
+pub(crate) fn pluralize<'a>(name: &'a str, options: &'a Options) -> String {
+    let (start, word) = split_last_word(name);
+    let lowercase = word.to_lowercase();
+
+    let overridden = options
+        .singulars
+        .iter()
+        .find(|(_, singular)| **singular == lowercase)
+        .map(|(plural, _)| plural.clone());
+    let plural = match overridden {
+        Some(plural) => plural,
+        None => match IRREGULAR
+            .iter()
+            .find(|(singular, _)| *singular == lowercase)
+        {
+            Some((_, plural)) => plural.to_string(),
+            None => pluralize_word(&lowercase),
+        },
+    };
+
+    start.to_string() + &write_capitalized(word, &plural)
+}
+
+fn singularize_word(word: &str) -> String {
+    if UNCOUNTABLE.contains(&word) {
+        return word.into();
+    }
+
+    if let Some(singular) = word.strip_suffix('s') {
+        // Like `caches`, but not `beaches` or `coaches`.
+        if E_SINGULARS.contains(&singular)
+            || singular.ends_with("ache") && !singular[..singular.len() - 4].ends_with(['e', 'o'])
+        {
+            return singular.into();
+        }
+    }
+
+    if word.len() > 4 && word.ends_with("ies") {
+        return word[..word.len() - 3].to_string() + "y";
+    }
+
+    if ["sses", "xes", "ches", "shes", "zzes", "iases"]
+        .iter()
+        .any(|end| word.ends_with(end))
+        || word.ends_with("uses") && !ends_with_vowel(&word[..word.len() - 4])
+    {
+        return word[..word.len() - 2].into();
+    }
+
+    if ["ss", "us", "is"].iter().any(|end| word.ends_with(end)) {
+        return word.into();
+    }
+
+    match word.strip_suffix('s') {
+        Some(singular) if !singular.is_empty() => singular.into(),
+        _ => word.into(),
+    }
+}
+
+fn pluralize_word(word: &str) -> String {
+    if UNCOUNTABLE.contains(&word) || word.is_empty() {
+        return word.into();
+    }
+
+    if let Some(start) = word.strip_suffix('y') {
+        if !ends_with_vowel(start) {
+            return start.to_string() + "ies";
+        }
+    }
+
+    if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|end| word.ends_with(end))
+    {
+        return word.to_string() + "es";
+    }
+
+    word.to_string() + "s"
+}
+
+fn ends_with_vowel(word: &str) -> bool {
+    word.ends_with(['a', 'e', 'i', 'o', 'u'])
+}
+
+/// Splits a PascalCase name before its last word.
+fn split_last_word(name: &str) -> (&str, &str) {
+    let index = name
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_uppercase())
+        .map(|(index, _)| index)
+        .unwrap_or(0);
+
+    name.split_at(index)
+}
+
+/// Writes a word with the capitalization of the word it replaces.
+fn write_capitalized(original: &str, word: &str) -> String {
+    let mut chars = word.chars();
+
+    match (original.starts_with(char::is_uppercase), chars.next()) {
+        (true, Some(first)) => first.to_uppercase().chain(chars).collect(),
+        _ => word.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::options::Options;
+
+    use super::{pluralize, singularize};
+
+    #[test]
+    fn singularizes_words() {
+        let words = [
+            ("movies", "movie"),
+            ("cookies", "cookie"),
+            ("categories", "category"),
+            ("caches", "cache"),
+            ("headaches", "headache"),
+            ("beaches", "beach"),
+            ("coaches", "coach"),
+            ("matches", "match"),
+            ("aliases", "alias"),
+            ("biases", "bias"),
+            ("databases", "database"),
+            ("statuses", "status"),
+            ("houses", "house"),
+            ("classes", "class"),
+            ("news", "news"),
+        ];
+
+        for (plural, singular) in words {
+            assert_eq!(singularize(plural, &Options::default()), singular);
+            assert_eq!(pluralize(singular, &Options::default()), plural);
+        }
+    }
+
+    #[test]
+    fn singularizes_the_last_word() {
+        let options = Options::default();
+
+        assert_eq!(singularize("FavoriteMovies", &options), "FavoriteMovie");
+        assert_eq!(pluralize("UserAlias", &options), "UserAliases");
+    }
+}
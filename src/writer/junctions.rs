@@ -0,0 +1,952 @@
+use convert_case::{Case, Casing};
+
+use crate::{
+    database::{
+        column::Column,
+        keys::{ForeignKey, Junction},
+        schema::Schema,
+        table::Table,
+    },
+    options::Options,
+};
+
+use super::{
+    column::{
+        has_default_expression, is_inserted, write_full_comment, write_row_value,
+        write_typed_variable, write_variable,
+    },
+    creater::{
+        is_optional, write_insert, write_inserted_value, write_parameter, write_parameter_comment,
+    },
+    foreign_getters::write_foreign_name,
+    inflection::pluralize,
+    naming::{write_property_name, write_table_class_name, write_table_variable},
+    params::{
+        write_query_execute, write_query_execute_of, write_query_params, write_query_params_of,
+    },
+    sql::{write_identifier, write_parameter_name},
+};
+
+/// Adds every junction table to the two tables it links. A junction table has
+/// a primary key made up of exactly the columns of two foreign keys, like
+/// `user_roles(user_id, role_id)`, and can have more columns.
+pub(crate) fn collect_junctions(schema: &mut Schema) {
+    let mut table_names = schema.tables.keys().cloned().collect::<Vec<_>>();
+    table_names.sort();
+
+    for table_name in &table_names {
+        let Some((foreign_key, other_foreign_key)) =
+            find_junction(&schema.tables[table_name], schema)
+        else {
+            continue;
+        };
+
+        let foreign_table_name = foreign_key.foreign_table_name.clone();
+        let other_foreign_table_name = other_foreign_key.foreign_table_name.clone();
+        let (foreign_key_name, other_foreign_key_name) =
+            (foreign_key.name.clone(), other_foreign_key.name.clone());
+
+        schema
+            .tables
+            .get_mut(&foreign_table_name)
+            .unwrap()
+            .junctions
+            .push(Junction::new(
+                table_name.clone(),
+                foreign_key_name.clone(),
+                other_foreign_key_name.clone(),
+            ));
+        schema
+            .tables
+            .get_mut(&other_foreign_table_name)
+            .unwrap()
+            .junctions
+            .push(Junction::new(
+                table_name.clone(),
+                other_foreign_key_name,
+                foreign_key_name,
+            ));
+    }
+}
+
+/// The two foreign keys making up the primary key of a junction table.
+fn find_junction<'a>(
+    table: &'a Table,
+    schema: &'a Schema,
+) -> Option<(&'a ForeignKey, &'a ForeignKey)> {
+    let primary_key = table.primary_key.as_ref()?;
+
+    // Both ends need the columns the foreign keys reference.
+    let foreign_keys = table
+        .iter_foreign_keys()
+        .filter(|foreign_key| {
+            schema
+                .tables
+                .get(&foreign_key.foreign_table_name)
+                .is_some_and(|foreign_table| {
+                    foreign_key
+                        .iter_foreign_column_names()
+                        .all(|column_name| foreign_table.columns.contains_key(column_name))
+                })
+        })
+        .collect::<Vec<_>>();
+
+    for (index, foreign_key) in foreign_keys.iter().enumerate() {
+        for other_foreign_key in &foreign_keys[index + 1..] {
+            let column_names = foreign_key
+                .iter_column_names()
+                .chain(other_foreign_key.iter_column_names())
+                .collect::<Vec<_>>();
+
+            let is_primary_key = column_names.len() == primary_key.column_names.len()
+                && primary_key
+                    .iter_column_names()
+                    .all(|column_name| column_names.contains(&column_name));
+
+            if is_primary_key {
+                return Some((foreign_key, other_foreign_key));
+            }
+        }
+    }
+
+    None
+}
+
+/// The names of the methods of a junction, for `User`: `getRoles`,
+/// `attachRole`, `detachRole`, `syncRoles` and `getUsersByRole`, the static
+/// getter `getUsers` of `Role` calls.
+pub(crate) fn write_junction_names<'a>(
+    junction: &'a Junction,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> Vec<String> {
+    if !junction.method_names.is_empty() {
+        return junction.method_names.clone();
+    }
+
+    let (name, other_name) = write_end_names(junction, schema, options);
+
+    vec![
+        "get".to_string() + &pluralize(&other_name, options),
+        "attach".to_string() + &other_name,
+        "detach".to_string() + &other_name,
+        "sync".to_string() + &pluralize(&other_name, options),
+        "get".to_string() + &pluralize(&name, options) + "By" + &other_name,
+    ]
+}
+
+pub(crate) fn write_junctions<'a>(
+    table: &'a Table,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
+    let mut buffer = String::new();
+
+    for junction in &table.junctions {
+        buffer += &write_getter(table, junction, schema, options);
+        buffer += &write_attacher(table, junction, schema, options);
+        buffer += &write_detacher(table, junction, schema, options);
+        buffer += &write_syncer(table, junction, schema, options);
+        buffer += &write_static_getter(table, junction, schema, options);
+    }
+
+    buffer
+}
+
+/// The foreign key referencing this table and the one referencing the other.
+fn resolve<'a>(junction: &'a Junction, schema: &'a Schema) -> (&'a ForeignKey, &'a ForeignKey) {
+    let junction_table = &schema.tables[&junction.table_name];
+
+    (
+        &junction_table.foreign_keys[&junction.foreign_key_name],
+        &junction_table.foreign_keys[&junction.other_foreign_key_name],
+    )
+}
+
+/// The variable of an object of the other table, like `$role`.
+fn write_other_variable<'a>(
+    junction: &'a Junction,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
+    let (_, other_name) = write_end_names(junction, schema, options);
+
+    "$".to_string() + &other_name.to_case(Case::Camel)
+}
+
+/// The names of this and the other end of a junction, after their foreign
+/// keys. The ends of a junction between rows of the same table are told apart
+/// by their columns, like `Follower` and `Followee` for
+/// `follows(follower, followee)`.
+fn write_end_names<'a>(
+    junction: &'a Junction,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> (String, String) {
+    let (foreign_key, other_foreign_key) = resolve(junction, schema);
+
+    if foreign_key.foreign_table_name != other_foreign_key.foreign_table_name {
+        return (
+            write_foreign_name(foreign_key, schema, options),
+            write_foreign_name(other_foreign_key, schema, options),
+        );
+    }
+
+    let write_name = |foreign_key: &ForeignKey| match foreign_key.column_names.as_slice() {
+        [column_name] => {
+            let name = column_name.to_case(Case::Snake);
+            let name = name
+                .strip_suffix("_id")
+                .filter(|name| !name.is_empty())
+                .unwrap_or(&name);
+
+            name.to_case(Case::Pascal)
+        }
+        _ => foreign_key.name.to_case(Case::Pascal),
+    };
+
+    (write_name(foreign_key), write_name(other_foreign_key))
+}
+
+/// The columns of the junction table with the value of an object, like
+/// `$this->id` for `user_id`.
+fn write_key_values<'a>(
+    foreign_key: &'a ForeignKey,
+    junction_table: &'a Table,
+    foreign_table: &'a Table,
+    object: &'a str,
+) -> Vec<(&'a Column, String)> {
+    foreign_key
+        .iter_column_names()
+        .zip(foreign_key.iter_foreign_column_names())
+        .map(|(column_name, foreign_column_name)| {
+            let value = format!(
+                "{}->{}",
+                object,
+                write_property_name(&foreign_table.columns[foreign_column_name])
+            );
+
+            (&junction_table.columns[column_name], value)
+        })
+        .collect()
+}
+
+/// The columns of a junction table besides the foreign keys, passed when
+/// attaching. The ones with a default go last so they can be left out.
+fn iter_pivot_columns<'a>(
+    junction_table: &'a Table,
+    options: &'a Options,
+) -> impl Iterator<Item = &'a Column> {
+    let primary_key = junction_table.primary_key.as_ref().unwrap();
+
+    let (optional, required): (Vec<_>, Vec<_>) = junction_table
+        .iter_columns()
+        .filter(|column| !primary_key.column_names.contains(&column.name))
+        .filter(|column| is_inserted(column) || has_default_expression(column))
+        .partition(|column| is_optional(column, options));
+
+    required.into_iter().chain(optional)
+}
+
+fn write_getter<'a>(
+    table: &'a Table,
+    junction: &'a Junction,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
+    let mut buffer = String::new();
+
+    let (foreign_key, other_foreign_key) = resolve(junction, schema);
+    let other_table = &schema.tables[&other_foreign_key.foreign_table_name];
+    let other_class_name = write_table_class_name(other_table);
+
+    // The static getter of the other table for this side of the junction.
+    let other_junction = other_table
+        .junctions
+        .iter()
+        .find(|other| {
+            other.table_name == junction.table_name
+                && other.foreign_key_name == junction.other_foreign_key_name
+        })
+        .unwrap();
+
+    buffer += "\n\t/** Gets the `";
+    buffer += &other_class_name;
+    buffer += "`s linked to this `";
+    buffer += &write_table_class_name(table);
+    buffer += "` through `";
+    buffer += &junction.table_name;
+    buffer += "`.\n";
+    buffer += "\t * @return array zero or more corresponding objects.\n";
+    buffer += "\t */\n";
+
+    buffer += "\tpublic function ";
+    buffer += &write_junction_names(junction, schema, options)[0];
+    buffer += "(): array\n";
+    buffer += "\t{\n";
+
+    let columns = foreign_key
+        .iter_foreign_column_names()
+        .map(|column_name| &table.columns[column_name])
+        .collect::<Vec<_>>();
+
+    let nullable_columns = columns
+        .iter()
+        .filter(|column| column.nullable)
+        .collect::<Vec<_>>();
+    if !nullable_columns.is_empty() {
+        buffer += "\t\tif (!isset(";
+        for (index, column) in nullable_columns.into_iter().enumerate() {
+            if index > 0 {
+                buffer += ", ";
+            }
+            buffer += "$this->";
+            buffer += &write_property_name(column);
+        }
+        buffer += "))\n";
+        buffer += "\t\t\treturn array();\n\n";
+    }
+
+    buffer += "\t\treturn ";
+    buffer += &other_class_name;
+    buffer += "::";
+    buffer += &write_junction_names(other_junction, schema, options)[4];
+    buffer += "(";
+    for (index, column) in columns.into_iter().enumerate() {
+        if index > 0 {
+            buffer += ", ";
+        }
+        buffer += "$this->";
+        buffer += &write_property_name(column);
+    }
+    buffer += ");\n";
+
+    buffer += "\t}\n";
+
+    buffer
+}
+
+fn write_attacher<'a>(
+    table: &'a Table,
+    junction: &'a Junction,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
+    let mut buffer = String::new();
+
+    let junction_table = &schema.tables[&junction.table_name];
+    let (foreign_key, other_foreign_key) = resolve(junction, schema);
+    let other_table = &schema.tables[&other_foreign_key.foreign_table_name];
+    let other_class_name = write_table_class_name(other_table);
+    let other_variable = write_other_variable(junction, schema, options);
+
+    buffer += "\n\t/** Links a `";
+    buffer += &other_class_name;
+    buffer += "` to this `";
+    buffer += &write_table_class_name(table);
+    buffer += "` through `";
+    buffer += &junction.table_name;
+    buffer += "`.\n";
+    buffer += "\t * @param ";
+    buffer += &other_class_name;
+    buffer += " ";
+    buffer += &other_variable;
+    buffer += "\n";
+    for column in iter_pivot_columns(junction_table, options) {
+        buffer += "\t * @param ";
+        buffer += &write_parameter_comment(column, options);
+        buffer += "\n";
+    }
+    buffer += "\t */\n";
+
+    buffer += "\tpublic function ";
+    buffer += &write_junction_names(junction, schema, options)[1];
+    buffer += "(";
+    buffer += &other_class_name;
+    buffer += " ";
+    buffer += &other_variable;
+    for column in iter_pivot_columns(junction_table, options) {
+        buffer += ", ";
+        buffer += &write_parameter(column, options);
+    }
+    buffer += "): void\n";
+    buffer += "\t{\n";
+
+    let mut values = write_key_values(foreign_key, junction_table, table, "$this");
+    values.extend(write_key_values(
+        other_foreign_key,
+        junction_table,
+        other_table,
+        &other_variable,
+    ));
+    values.extend(
+        iter_pivot_columns(junction_table, options)
+            .map(|column| (column, write_inserted_value(column, options))),
+    );
+
+    buffer += &write_insert(junction_table, &values, schema.dialect, options);
+
+    buffer += "\t}\n";
+
+    buffer
+}
+
+fn write_detacher<'a>(
+    table: &'a Table,
+    junction: &'a Junction,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
+    let mut buffer = String::new();
+
+    let junction_table = &schema.tables[&junction.table_name];
+    let (foreign_key, other_foreign_key) = resolve(junction, schema);
+    let other_table = &schema.tables[&other_foreign_key.foreign_table_name];
+    let other_class_name = write_table_class_name(other_table);
+    let other_variable = write_other_variable(junction, schema, options);
+
+    buffer += "\n\t/** Unlinks a `";
+    buffer += &other_class_name;
+    buffer += "` from this `";
+    buffer += &write_table_class_name(table);
+    buffer += "` through `";
+    buffer += &junction.table_name;
+    buffer += "`.\n";
+    buffer += "\t * @param ";
+    buffer += &other_class_name;
+    buffer += " ";
+    buffer += &other_variable;
+    buffer += "\n";
+    buffer += "\t */\n";
+
+    buffer += "\tpublic function ";
+    buffer += &write_junction_names(junction, schema, options)[2];
+    buffer += "(";
+    buffer += &other_class_name;
+    buffer += " ";
+    buffer += &other_variable;
+    buffer += "): void\n";
+    buffer += "\t{\n";
+
+    let mut values = write_key_values(foreign_key, junction_table, table, "$this");
+    values.extend(write_key_values(
+        other_foreign_key,
+        junction_table,
+        other_table,
+        &other_variable,
+    ));
+
+    buffer += &write_query_params_of(&values, options);
+
+    let dialect = schema.dialect;
+    buffer += "\t\t$sth = getPDO()->prepare(\"DELETE FROM ";
+    buffer += &write_identifier(dialect, &junction_table.name);
+    buffer += " WHERE ";
+    for (index, (column, _)) in values.iter().enumerate() {
+        if index > 0 {
+            buffer += " AND ";
+        }
+        buffer += &write_identifier(dialect, &column.name);
+        buffer += " = :";
+        buffer += &write_parameter_name(&column.name);
+    }
+    buffer += ";\");\n";
+
+    buffer += &write_query_execute_of(&values, options);
+
+    buffer += "\t}\n";
+
+    buffer
+}
+
+/// Links exactly the given objects, the links that stay keep their pivot
+/// columns. The pivot columns of the added links are passed like when attaching.
+fn write_syncer<'a>(
+    table: &'a Table,
+    junction: &'a Junction,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
+    let mut buffer = String::new();
+
+    let junction_table = &schema.tables[&junction.table_name];
+    let (_, other_foreign_key) = resolve(junction, schema);
+    let other_table = &schema.tables[&other_foreign_key.foreign_table_name];
+    let other_class_name = write_table_class_name(other_table);
+    let other_variable = write_other_variable(junction, schema, options);
+    let names = write_junction_names(junction, schema, options);
+
+    // Uncountable names are the same in plural.
+    let (_, other_name) = write_end_names(junction, schema, options);
+    let mut plural_variable =
+        "$".to_string() + &pluralize(&other_name, options).to_case(Case::Camel);
+    if plural_variable == other_variable {
+        plural_variable += "List";
+    }
+
+    buffer += "\n\t/** Links exactly these `";
+    buffer += &other_class_name;
+    buffer += "`s to this `";
+    buffer += &write_table_class_name(table);
+    buffer += "` through `";
+    buffer += &junction.table_name;
+    buffer += "`, unlinking the others.\n";
+    if iter_pivot_columns(junction_table, options).next().is_some() {
+        buffer += "\t * The added links get the pivot values.\n";
+    }
+    buffer += "\t * @param array<";
+    buffer += &other_class_name;
+    buffer += "> ";
+    buffer += &plural_variable;
+    buffer += "\n";
+    for column in iter_pivot_columns(junction_table, options) {
+        buffer += "\t * @param ";
+        buffer += &write_parameter_comment(column, options);
+        buffer += "\n";
+    }
+    buffer += "\t */\n";
+
+    buffer += "\tpublic function ";
+    buffer += &names[3];
+    buffer += "(array ";
+    buffer += &plural_variable;
+    for column in iter_pivot_columns(junction_table, options) {
+        buffer += ", ";
+        buffer += &write_parameter(column, options);
+    }
+    buffer += "): void\n";
+    buffer += "\t{\n";
+
+    // Objects are compared by the columns the junction table references.
+    buffer += "\t\t$key = fn (";
+    buffer += &other_class_name;
+    buffer += " ";
+    buffer += &other_variable;
+    buffer += ") => array(";
+    for (index, column_name) in other_foreign_key.iter_foreign_column_names().enumerate() {
+        if index > 0 {
+            buffer += ", ";
+        }
+        buffer += &other_variable;
+        buffer += "->";
+        buffer += &write_property_name(&other_table.columns[column_name]);
+    }
+    buffer += ");\n";
+
+    buffer += "\t\t$current = $this->";
+    buffer += &names[0];
+    buffer += "();\n\n";
+
+    buffer += "\t\tforeach ($current as ";
+    buffer += &other_variable;
+    buffer += ")\n";
+    buffer += "\t\t\tif (!in_array($key(";
+    buffer += &other_variable;
+    buffer += "), array_map($key, ";
+    buffer += &plural_variable;
+    buffer += ")))\n";
+    buffer += "\t\t\t\t$this->";
+    buffer += &names[2];
+    buffer += "(";
+    buffer += &other_variable;
+    buffer += ");\n\n";
+
+    buffer += "\t\tforeach (";
+    buffer += &plural_variable;
+    buffer += " as ";
+    buffer += &other_variable;
+    buffer += ")\n";
+    buffer += "\t\t\tif (!in_array($key(";
+    buffer += &other_variable;
+    buffer += "), array_map($key, $current)))\n";
+    buffer += "\t\t\t\t$this->";
+    buffer += &names[1];
+    buffer += "(";
+    buffer += &other_variable;
+    for column in iter_pivot_columns(junction_table, options) {
+        buffer += ", ";
+        buffer += &write_variable(column);
+    }
+    buffer += ");\n";
+
+    buffer += "\t}\n";
+
+    buffer
+}
+
+/// Gets the objects of this table linked to an object of the other table, with
+/// a single `JOIN`.
+fn write_static_getter<'a>(
+    table: &'a Table,
+    junction: &'a Junction,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
+    let mut buffer = String::new();
+
+    let junction_table = &schema.tables[&junction.table_name];
+    let (foreign_key, other_foreign_key) = resolve(junction, schema);
+    let other_table = &schema.tables[&other_foreign_key.foreign_table_name];
+    let columns = other_foreign_key
+        .iter_columns(junction_table)
+        .collect::<Vec<_>>();
+
+    buffer += "\n\t/** Gets the `";
+    buffer += &write_table_class_name(table);
+    buffer += "`s linked to a `";
+    buffer += &write_table_class_name(other_table);
+    buffer += "` through `";
+    buffer += &junction.table_name;
+    buffer += "`.\n";
+    for column in &columns {
+        buffer += "\t * @param ";
+        buffer += &write_full_comment(column, options);
+        buffer += "\n";
+    }
+    buffer += "\t * @return array zero or more corresponding objects.\n";
+    buffer += "\t */\n";
+
+    buffer += "\tpublic static function ";
+    buffer += &write_junction_names(junction, schema, options)[4];
+    buffer += "(";
+    for (index, column) in columns.iter().enumerate() {
+        if index > 0 {
+            buffer += ", ";
+        }
+        buffer += &write_typed_variable(column, options);
+    }
+    buffer += "): array\n";
+    buffer += "\t{\n";
+
+    buffer += &write_query_params(&columns, options);
+
+    let dialect = schema.dialect;
+    let qualify = |table_name: &str, column_name: &str| {
+        write_identifier(dialect, table_name) + "." + &write_identifier(dialect, column_name)
+    };
+
+    buffer += "\t\t$sth = getPDO()->prepare(\"SELECT ";
+    buffer += &write_identifier(dialect, &table.name);
+    buffer += ".* FROM ";
+    buffer += &write_identifier(dialect, &table.name);
+    buffer += " INNER JOIN ";
+    buffer += &write_identifier(dialect, &junction_table.name);
+    buffer += " ON ";
+    for (index, (column_name, foreign_column_name)) in foreign_key
+        .iter_column_names()
+        .zip(foreign_key.iter_foreign_column_names())
+        .enumerate()
+    {
+        if index > 0 {
+            buffer += " AND ";
+        }
+        buffer += &qualify(&junction_table.name, column_name);
+        buffer += " = ";
+        buffer += &qualify(&table.name, foreign_column_name);
+    }
+    buffer += " WHERE ";
+    for (index, column) in columns.iter().enumerate() {
+        if index > 0 {
+            buffer += " AND ";
+        }
+        buffer += &qualify(&junction_table.name, &column.name);
+        buffer += " = :";
+        buffer += &write_parameter_name(&column.name);
+    }
+    buffer += ";\");\n";
+
+    buffer += &write_query_execute(&columns, options);
+    buffer += "\n";
+
+    let variable = write_table_variable(&table.name);
+
+    buffer += "\t\t$";
+    buffer += &variable;
+    buffer += " = array();\n\n";
+    buffer += "\t\twhile ($row = $sth->fetch())\n";
+    buffer += "\t\t\t$";
+    buffer += &variable;
+    buffer += "[] = new ";
+    buffer += &write_table_class_name(table);
+    buffer += "(";
+    for (index, column) in table.iter_columns().enumerate() {
+        if index > 0 {
+            buffer += ", ";
+        }
+        buffer += &write_row_value(column, options);
+    }
+    buffer += ");\n\n";
+
+    buffer += "\t\treturn $";
+    buffer += &variable;
+    buffer += ";\n";
+
+    buffer += "\t}\n";
+
+    buffer
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        database::{
+            column::{Column, ColumnDefault, ColumnType},
+            dialect::SQLDialect,
+            keys::{ForeignKey, Key},
+            schema::Schema,
+            table::Table,
+        },
+        diagnostics::Diagnostics,
+        options::Options,
+        parsers::parse_source,
+        writer::naming::name_members,
+    };
+
+    use super::{
+        collect_junctions, write_attacher, write_detacher, write_junctions, write_static_getter,
+    };
+
+    /// Adds a table of `INT NOT NULL` columns, with their numeric default.
+    fn add_table(
+        schema: &mut Schema,
+        name: &str,
+        columns: &[(&str, Option<&str>)],
+        primary_key: &[&str],
+    ) {
+        let mut table = Table::new(name.into(), Vec::new(), HashMap::new());
+
+        for (column_name, default) in columns {
+            let column = Column::new(
+                column_name.to_string(),
+                ColumnType::Int,
+                false,
+                false,
+                "".into(),
+                default.map(|default| ColumnDefault::Number(default.into())),
+            );
+
+            table.column_names.push(column_name.to_string());
+            table.columns.insert(column_name.to_string(), column);
+        }
+
+        let column_names = primary_key.iter().map(|name| name.to_string()).collect();
+        table.primary_key = Some(Key::new("".into(), true, name.into(), column_names));
+
+        schema.tables.insert(name.into(), table);
+    }
+
+    fn add_foreign_key(schema: &mut Schema, table_name: &str, column_name: &str, foreign: &str) {
+        let foreign_key = ForeignKey::new(
+            format!("{}_{}", table_name, column_name),
+            table_name.into(),
+            vec![column_name.into()],
+            foreign.into(),
+            vec!["id".into()],
+        );
+
+        let table = schema.tables.get_mut(table_name).unwrap();
+        table.foreign_key_names.push(foreign_key.name.clone());
+        table
+            .foreign_keys
+            .insert(foreign_key.name.clone(), foreign_key);
+    }
+
+    /// `users` and `roles` linked by `user_roles`, with a `level` pivot column.
+    fn create_schema() -> Schema {
+        let mut schema = Schema::new(SQLDialect::MariaDB);
+
+        add_table(&mut schema, "users", &[("id", None)], &["id"]);
+        add_table(&mut schema, "roles", &[("id", None)], &["id"]);
+        add_table(
+            &mut schema,
+            "user_roles",
+            &[("user_id", None), ("role_id", None), ("level", Some("0"))],
+            &["user_id", "role_id"],
+        );
+        add_foreign_key(&mut schema, "user_roles", "user_id", "users");
+        add_foreign_key(&mut schema, "user_roles", "role_id", "roles");
+
+        collect_junctions(&mut schema);
+
+        let mut diagnostics = Diagnostics::new(false);
+        name_members(&mut schema, &Options::default(), &mut diagnostics);
+        assert!(diagnostics.is_empty());
+
+        schema
+    }
+
+    #[test]
+    fn links_both_tables_of_a_junction() {
+        let schema = create_schema();
+
+        for (table_name, other_table_name) in [("users", "roles"), ("roles", "users")] {
+            let junctions = &schema.tables[table_name].junctions;
+
+            assert_eq!(junctions.len(), 1);
+            assert_eq!(junctions[0].table_name, "user_roles");
+            assert_eq!(
+                schema.tables["user_roles"].foreign_keys[&junctions[0].other_foreign_key_name]
+                    .foreign_table_name,
+                other_table_name
+            );
+        }
+        assert!(schema.tables["user_roles"].junctions.is_empty());
+    }
+
+    #[test]
+    fn attaches_with_the_pivot_columns() {
+        let schema = create_schema();
+        let users = &schema.tables["users"];
+
+        assert_eq!(
+            write_attacher(users, &users.junctions[0], &schema, &Options::default()),
+            "
+	/** Links a `Role` to this `User` through `user_roles`.
+	 * @param Role $role
+	 * @param int $level
+	 */
+	public function attachRole(Role $role, int $level = 0): void
+	{
+		$params = array(
+			\":user_id\" => $this->id,
+			\":role_id\" => $role->id,
+			\":level\" => $level,
+		);
+		$sth = getPDO()->prepare(\"INSERT INTO `user_roles` (`user_id`, `role_id`, `level`) VALUES (:user_id, :role_id, :level);\");
+		$sth->execute($params);
+	}
+"
+        );
+    }
+
+    #[test]
+    fn detaches_by_both_keys() {
+        let schema = create_schema();
+        let users = &schema.tables["users"];
+
+        assert_eq!(
+            write_detacher(users, &users.junctions[0], &schema, &Options::default()),
+            "
+	/** Unlinks a `Role` from this `User` through `user_roles`.
+	 * @param Role $role
+	 */
+	public function detachRole(Role $role): void
+	{
+		$params = array(
+			\":user_id\" => $this->id,
+			\":role_id\" => $role->id,
+		);
+		$sth = getPDO()->prepare(\"DELETE FROM `user_roles` WHERE `user_id` = :user_id AND `role_id` = :role_id;\");
+		$sth->execute($params);
+	}
+"
+        );
+    }
+
+    #[test]
+    fn gets_the_linked_rows_with_one_join() {
+        let schema = create_schema();
+        let users = &schema.tables["users"];
+
+        assert_eq!(
+            write_static_getter(users, &users.junctions[0], &schema, &Options::default()),
+            "
+	/** Gets the `User`s linked to a `Role` through `user_roles`.
+	 * @param int $roleId
+	 * @return array zero or more corresponding objects.
+	 */
+	public static function getUsersByRole(int $roleId): array
+	{
+		$params = array(\":role_id\" => $roleId);
+		$sth = getPDO()->prepare(\"SELECT `users`.* FROM `users` INNER JOIN `user_roles` ON `user_roles`.`user_id` = `users`.`id` WHERE `user_roles`.`role_id` = :role_id;\");
+		$sth->execute($params);
+
+		$users = array();
+
+		while ($row = $sth->fetch())
+			$users[] = new User((int)$row[\"id\"]);
+
+		return $users;
+	}
+"
+        );
+    }
+
+    fn write_user_junctions(pivot_columns: &str) -> String {
+        let mut schema = parse_source(
+            &format!(
+                "CREATE TABLE users (id INT PRIMARY KEY);
+                CREATE TABLE roles (id INT PRIMARY KEY);
+                CREATE TABLE user_roles (user_id INT REFERENCES users (id), role_id INT REFERENCES roles (id){}, PRIMARY KEY (user_id, role_id));",
+                pivot_columns
+            ),
+            SQLDialect::MariaDB,
+        );
+        collect_junctions(&mut schema);
+
+        write_junctions(&schema.tables["users"], &schema, &Options::default())
+    }
+
+    #[test]
+    fn syncs_without_pivot_columns() {
+        let junctions = write_user_junctions("");
+
+        assert!(junctions.contains("public function syncRoles(array $roles): void"));
+        assert!(junctions.contains("$this->attachRole($role);"));
+    }
+
+    #[test]
+    fn syncs_with_required_pivot_columns() {
+        let junctions = write_user_junctions(", granted_by INT NOT NULL, note TEXT DEFAULT ''");
+
+        assert!(junctions.contains(
+            "public function syncRoles(array $roles, int $grantedBy, null|string $note = \"\"): void"
+        ));
+        assert!(junctions.contains("$this->attachRole($role, $grantedBy, $note);"));
+    }
+
+    #[test]
+    fn attaches_default_expressions_only_when_passed() {
+        let junctions =
+            write_user_junctions(", granted_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP");
+
+        assert!(junctions.contains(
+            "public function attachRole(Roles $role, null|DateTimeImmutable $grantedAt = null): void"
+        ));
+        assert!(junctions.contains("if ($grantedAt !== null) {"));
+        assert!(junctions.contains(
+            "INSERT INTO `user_roles` (\" . implode(\", \", $columns) . \") VALUES (\" . implode(\", \", array_keys($params)) . \");"
+        ));
+    }
+
+    #[test]
+    fn names_the_ends_of_self_junctions_after_their_columns() {
+        let mut schema = parse_source(
+            "CREATE TABLE users (id INT PRIMARY KEY);
+            CREATE TABLE follows (follower INT NOT NULL REFERENCES users (id), followee INT NOT NULL REFERENCES users (id), PRIMARY KEY (follower, followee));
+            CREATE TABLE friendships (user_id INT NOT NULL REFERENCES users (id), friend_id INT NOT NULL REFERENCES users (id), PRIMARY KEY (user_id, friend_id));",
+            SQLDialect::MariaDB,
+        );
+        collect_junctions(&mut schema);
+
+        let mut diagnostics = Diagnostics::new(false);
+        name_members(&mut schema, &Options::default(), &mut diagnostics);
+
+        let users = &schema.tables["users"];
+        let names = users
+            .junctions
+            .iter()
+            .map(|junction| junction.method_names[0].as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["getFollowees", "getFollowers", "getFriends", "getUsers"]
+        );
+
+        let junctions = write_junctions(users, &schema, &Options::default());
+        assert!(junctions.contains("return User::getFriendsByUser($this->id);"));
+        assert!(junctions.contains("$this->attachFollower($follower);"));
+    }
+}
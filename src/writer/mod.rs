@@ -1,8 +1,19 @@
-mod column;
+pub(crate) mod boilerplate;
+pub(crate) mod column;
 mod creater;
 mod deleter;
+pub(crate) mod enums;
 mod foreign_getters;
 mod get_all;
 mod getters;
+mod inflection;
+pub(crate) mod junctions;
+pub(crate) mod naming;
+mod params;
+mod php;
+pub(crate) mod references;
+mod sql;
 pub(crate) mod table;
+pub(crate) mod trees;
 mod updater;
+mod withers;
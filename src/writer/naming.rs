@@ -0,0 +1,654 @@
+use convert_case::{Case, Casing};
+use sqlparser::tokenizer::Span;
+
+use crate::{
+    database::{column::Column, schema::Schema, table::Table},
+    diagnostics::Diagnostics,
+    options::{NameConflicts, Options},
+};
+
+use super::{
+    enums::collect_enums,
+    foreign_getters::write_foreign_getter_name,
+    getters::{write_getter_name, write_json_path_getter_name},
+    inflection::singularize,
+    junctions::write_junction_names,
+    references::write_reference_name,
+    trees::{iter_tree_foreign_keys, write_tree_names},
+    withers::{iter_changeable, write_wither_name},
+};
+
+/// Writes the PHP class name of a table, the parts of schema qualified names are joined.
+pub(crate) fn write_class_name(table_name: &str) -> String {
+    table_name.replace('.', "_").to_case(Case::Pascal)
+}
+
+/// Writes the default PHP class of a table, without the configured prefix and
+/// singular, like `Post` for `wp_posts`.
+pub(crate) fn write_default_class_name<'a>(table_name: &'a str, options: &'a Options) -> String {
+    let table_name = options
+        .table_prefixes
+        .iter()
+        .find_map(|prefix| table_name.strip_prefix(prefix.as_str()))
+        .filter(|table_name| !table_name.is_empty())
+        .unwrap_or(table_name);
+
+    singularize(&write_class_name(table_name), options)
+}
+
+/// Writes the default name of the enum of a column, like `StatusEnum`.
+pub(crate) fn write_enum_class_name(column_name: &str) -> String {
+    column_name.to_case(Case::Pascal) + "Enum"
+}
+
+/// Writes the name of a PHP variable holding the rows of a table.
+pub(crate) fn write_table_variable(table_name: &str) -> String {
+    table_name.replace('.', "_").to_case(Case::Camel)
+}
+
+/// Writes the PHP class of a table.
+pub(crate) fn write_table_class_name(table: &Table) -> String {
+    match &table.class_name {
+        Some(name) => name.clone(),
+        None => write_class_name(&table.name),
+    }
+}
+
+/// Writes the name of the file of a table, the class starting with a lowercase letter.
+pub(crate) fn write_table_file_name(table: &Table) -> String {
+    let class_name = write_table_class_name(table);
+    let mut chars = class_name.chars();
+
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => class_name,
+    }
+}
+
+/// Writes the PHP property of a column, without the `$`.
+pub(crate) fn write_property_name(column: &Column) -> String {
+    match &column.property_name {
+        Some(name) => name.clone(),
+        None => column.name.to_case(Case::Camel),
+    }
+}
+
+/// Class names PHP reserves, and classes the generated code uses.
+const RESERVED_CLASS_NAMES: &[&str] = &[
+    "abstract",
+    "and",
+    "array",
+    "as",
+    "bool",
+    "break",
+    "callable",
+    "case",
+    "catch",
+    "class",
+    "clone",
+    "const",
+    "continue",
+    "declare",
+    "default",
+    "do",
+    "echo",
+    "else",
+    "elseif",
+    "empty",
+    "enddeclare",
+    "endfor",
+    "endforeach",
+    "endif",
+    "endswitch",
+    "endwhile",
+    "eval",
+    "exit",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "fn",
+    "for",
+    "foreach",
+    "function",
+    "global",
+    "goto",
+    "if",
+    "implements",
+    "include",
+    "include_once",
+    "instanceof",
+    "insteadof",
+    "int",
+    "interface",
+    "isset",
+    "iterable",
+    "list",
+    "match",
+    "mixed",
+    "namespace",
+    "never",
+    "new",
+    "null",
+    "object",
+    "or",
+    "parent",
+    "print",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "require",
+    "require_once",
+    "return",
+    "self",
+    "static",
+    "string",
+    "switch",
+    "throw",
+    "trait",
+    "true",
+    "try",
+    "unset",
+    "use",
+    "var",
+    "void",
+    "while",
+    "xor",
+    "yield",
+    "DateTimeImmutable",
+    "DateTimeInterface",
+    "DateTimeZone",
+    "DomainException",
+    "PDO",
+    "UnknownEnumValueException",
+];
+
+/// Variables and properties the generated methods use next to the parameters.
+const RESERVED_VARIABLES: &[&str] = &["this", "params", "sth", "row", "columns", "changedColumns"];
+
+/// Methods every class has, besides the getters of the keys.
+const GENERATED_METHODS: &[&str] = &[
+    "__construct",
+    "get",
+    "getAll",
+    "register",
+    "update",
+    "save",
+    "saveChanges",
+    "delete",
+    "deleteByKey",
+];
+
+/// The names used in a PHP scope, like the classes or the properties of a class.
+struct Scope<'a> {
+    names: Vec<String>,
+    reserved: Vec<&'a str>,
+    /// Added to reserved names.
+    suffix: &'a str,
+    case_sensitive: bool,
+}
+
+impl Scope<'_> {
+    fn contains(&self, names: &[impl AsRef<str>], name: &str) -> bool {
+        names.iter().any(|other| match self.case_sensitive {
+            true => other.as_ref() == name,
+            false => other.as_ref().eq_ignore_ascii_case(name),
+        })
+    }
+
+    /// Takes the name of one of the methods generated for `path`, which
+    /// `--method-name` sets by `path.name`, like `users.user_roles.attachRole`.
+    fn take_method<'a>(
+        &mut self,
+        name: String,
+        kind: &'a str,
+        path: &'a str,
+        options: &'a Options,
+        diagnostics: &'a mut Diagnostics,
+    ) -> String {
+        let path = format!("{}.{}", path, name);
+        let name = options.method_names.get(&path).cloned().unwrap_or(name);
+
+        self.take(name, kind, &path, options, diagnostics)
+    }
+
+    /// Takes a name, reserved and used names get a suffix, or are reported as
+    /// an error with `--name-conflicts error`.
+    fn take<'a>(
+        &mut self,
+        name: String,
+        kind: &'a str,
+        path: &'a str,
+        options: &'a Options,
+        diagnostics: &'a mut Diagnostics,
+    ) -> String {
+        let mut base = name.clone();
+        let mut message = None;
+
+        if self.contains(&self.reserved, &name) {
+            base += self.suffix;
+            message = Some(format!("the {} `{}` of `{}` is reserved", kind, name, path));
+        }
+
+        let mut resolved = base.clone();
+        let mut suffix = 2;
+        while self.contains(&self.names, &resolved) {
+            resolved = format!("{}{}", base, suffix);
+            suffix += 1;
+        }
+        if resolved != base && message.is_none() {
+            message = Some(format!(
+                "the {} `{}` of `{}` is already used",
+                kind, name, path
+            ));
+        }
+
+        if let Some(message) = message {
+            match options.name_conflicts {
+                NameConflicts::Suffix => diagnostics.warning(
+                    Span::empty(),
+                    format!("{}, it is named `{}` instead", message, resolved),
+                ),
+                NameConflicts::Error => diagnostics.error(Span::empty(), message),
+            }
+        }
+
+        self.names.push(resolved.clone());
+
+        resolved
+    }
+}
+
+/// Names the classes, properties and getters of every table. Names that
+/// collide or are reserved in PHP are reported.
+pub(crate) fn name_members<'a>(
+    schema: &'a mut Schema,
+    options: &'a Options,
+    diagnostics: &'a mut Diagnostics,
+) {
+    let mut table_names = schema.tables.keys().cloned().collect::<Vec<_>>();
+    table_names.sort();
+
+    let mut classes = Scope {
+        // Enum files are named after the enum, tables can't use those names.
+        names: collect_enums(schema)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect(),
+        reserved: RESERVED_CLASS_NAMES.to_vec(),
+        suffix: "Table",
+        case_sensitive: false,
+    };
+
+    for table_name in &table_names {
+        let name = match options.class_names.get(table_name) {
+            Some(name) => name.clone(),
+            None => write_identifier_start(write_default_class_name(table_name, options)),
+        };
+
+        let name = classes.take(name, "class", table_name, options, diagnostics);
+
+        schema.tables.get_mut(table_name).unwrap().class_name = Some(name);
+    }
+
+    for table_name in &table_names {
+        // Getters of relations are named after other classes.
+        let table = &schema.tables[table_name];
+        let foreign_getter_names = table
+            .iter_foreign_keys()
+            .map(|foreign_key| write_foreign_getter_name(foreign_key, schema, options))
+            .collect();
+        let reference_names = table
+            .references
+            .iter()
+            .map(|reference| write_reference_name(table, reference, schema, options))
+            .collect();
+        let junction_names = table
+            .junctions
+            .iter()
+            .map(|junction| write_junction_names(junction, schema, options))
+            .collect();
+        let tree_names = iter_tree_foreign_keys(table)
+            .map(|foreign_key| {
+                (
+                    foreign_key.name.clone(),
+                    write_tree_names(foreign_key, schema, options),
+                )
+            })
+            .collect();
+
+        let table = schema.tables.get_mut(table_name).unwrap();
+
+        name_properties(table, options, diagnostics);
+        name_getters(
+            table,
+            foreign_getter_names,
+            reference_names,
+            junction_names,
+            tree_names,
+            options,
+            diagnostics,
+        );
+    }
+}
+
+fn name_properties<'a>(
+    table: &'a mut Table,
+    options: &'a Options,
+    diagnostics: &'a mut Diagnostics,
+) {
+    // The getters put their results in a variable named after the table.
+    let table_variable = write_table_variable(&table.name);
+
+    let mut properties = Scope {
+        names: Vec::new(),
+        reserved: RESERVED_VARIABLES.to_vec(),
+        suffix: "Value",
+        case_sensitive: true,
+    };
+    properties.reserved.push(&table_variable);
+
+    for column_name in &table.column_names {
+        let path = format!("{}.{}", table.name, column_name);
+        let name = match options.property_names.get(&path) {
+            Some(name) => name.clone(),
+            None => write_identifier_start(column_name.to_case(Case::Camel)),
+        };
+
+        let name = properties.take(name, "property", &path, options, diagnostics);
+
+        table.columns.get_mut(column_name).unwrap().property_name = Some(name);
+    }
+}
+
+fn name_getters<'a>(
+    table: &'a mut Table,
+    foreign_getter_names: Vec<String>,
+    reference_names: Vec<String>,
+    junction_names: Vec<Vec<String>>,
+    tree_names: Vec<(String, Vec<String>)>,
+    options: &'a Options,
+    diagnostics: &'a mut Diagnostics,
+) {
+    let mut methods = Scope {
+        names: GENERATED_METHODS
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        reserved: Vec::new(),
+        suffix: "",
+        case_sensitive: false,
+    };
+
+    for key_name in table.key_names.clone() {
+        let key = table.keys.get_mut(&key_name).unwrap();
+
+        let path = format!("{}.{}", table.name, key_name);
+        let name = match options.method_names.get(&path) {
+            Some(name) => name.clone(),
+            None => write_getter_name(key),
+        };
+
+        key.method_name = Some(methods.take(name, "getter", &path, options, diagnostics));
+    }
+
+    for (foreign_key_name, name) in table
+        .foreign_key_names
+        .clone()
+        .into_iter()
+        .zip(foreign_getter_names)
+    {
+        let foreign_key = table.foreign_keys.get_mut(&foreign_key_name).unwrap();
+
+        let path = format!("{}.{}", table.name, foreign_key_name);
+        let name = options.method_names.get(&path).cloned().unwrap_or(name);
+
+        foreign_key.method_name = Some(methods.take(name, "getter", &path, options, diagnostics));
+    }
+
+    for (reference, name) in table.references.iter_mut().zip(reference_names) {
+        let path = format!(
+            "{}.{}.{}",
+            table.name, reference.table_name, reference.foreign_key_name
+        );
+        let name = options.method_names.get(&path).cloned().unwrap_or(name);
+
+        reference.method_name = Some(methods.take(name, "getter", &path, options, diagnostics));
+    }
+
+    // Keys on a JSON path also get a getter by the path.
+    let json_path_names = table
+        .iter_keys()
+        .filter_map(|key| Some((key.name.clone(), write_json_path_getter_name(table, key)?)))
+        .collect::<Vec<_>>();
+    for (key_name, name) in json_path_names {
+        let path = format!("{}.{}", table.name, key_name);
+
+        table.keys.get_mut(&key_name).unwrap().json_path_method_name =
+            Some(methods.take_method(name, "getter", &path, options, diagnostics));
+    }
+
+    for (junction, names) in table.junctions.iter_mut().zip(junction_names) {
+        let path = format!("{}.{}", table.name, junction.table_name);
+
+        junction.method_names = names
+            .into_iter()
+            .map(|name| methods.take_method(name, "method", &path, options, diagnostics))
+            .collect();
+    }
+
+    for (foreign_key_name, names) in tree_names {
+        let path = format!("{}.{}", table.name, foreign_key_name);
+
+        table
+            .foreign_keys
+            .get_mut(&foreign_key_name)
+            .unwrap()
+            .tree_method_names = names
+            .into_iter()
+            .map(|name| methods.take_method(name, "getter", &path, options, diagnostics))
+            .collect();
+    }
+
+    let changeable_column_names = iter_changeable(table)
+        .map(|column| column.name.clone())
+        .collect::<Vec<_>>();
+    for column_name in changeable_column_names {
+        let column = table.columns.get_mut(&column_name).unwrap();
+
+        let path = format!("{}.{}", table.name, column_name);
+        let name = write_wither_name(column);
+
+        column.method_name =
+            Some(methods.take_method(name, "with-er", &path, options, diagnostics));
+    }
+}
+
+/// Identifiers can't start with a digit.
+fn write_identifier_start(name: String) -> String {
+    match name.starts_with(|c: char| c.is_ascii_digit()) || name.is_empty() {
+        true => "_".to_string() + &name,
+        false => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        database::{
+            column::{Column, ColumnType},
+            dialect::SQLDialect,
+            keys::Key,
+            schema::Schema,
+            table::Table,
+        },
+        diagnostics::Diagnostics,
+        options::{NameConflicts, Options},
+        parsers::parse_source,
+        writer::junctions::collect_junctions,
+    };
+
+    use super::name_members;
+
+    fn add_table(schema: &mut Schema, name: &str, column_names: &[&str]) {
+        let columns = column_names
+            .iter()
+            .map(|column_name| {
+                let column = Column::new(
+                    column_name.to_string(),
+                    ColumnType::String,
+                    false,
+                    false,
+                    "".into(),
+                    None,
+                );
+
+                (column_name.to_string(), column)
+            })
+            .collect();
+        let column_names = column_names.iter().map(|name| name.to_string()).collect();
+
+        let table = Table::new(name.into(), column_names, columns);
+
+        schema.tables.insert(name.into(), table);
+    }
+
+    #[test]
+    fn suffixes_reserved_and_taken_names() {
+        let mut schema = Schema::new(SQLDialect::MariaDB);
+        add_table(&mut schema, "class", &["row", "user_id", "userId", "class"]);
+        add_table(&mut schema, "order_item", &["id"]);
+        add_table(&mut schema, "order.item", &["id"]);
+
+        let mut diagnostics = Diagnostics::new(false);
+        name_members(&mut schema, &Options::default(), &mut diagnostics);
+
+        let class_names = ["class", "order.item", "order_item"]
+            .map(|name| schema.tables[name].class_name.clone().unwrap());
+        assert_eq!(class_names, ["ClassTable", "OrderItem", "OrderItem2"]);
+
+        let table = &schema.tables["class"];
+        let property_names = table
+            .iter_columns()
+            .map(|column| column.property_name.clone().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            property_names,
+            ["rowValue", "userId", "userId2", "classValue"]
+        );
+
+        assert!(!diagnostics.has_errors());
+        assert_eq!(
+            diagnostics.to_string(),
+            "warning: the class `Class` of `class` is reserved, it is named `ClassTable` instead\n\n\
+            warning: the class `OrderItem` of `order_item` is already used, it is named `OrderItem2` instead\n\n\
+            warning: the property `row` of `class.row` is reserved, it is named `rowValue` instead\n\n\
+            warning: the property `userId` of `class.userId` is already used, it is named `userId2` instead\n\n\
+            warning: the property `class` of `class.class` is reserved, it is named `classValue` instead\n\n"
+        );
+    }
+
+    #[test]
+    fn reports_conflicts_as_errors_unless_renamed() {
+        let mut schema = Schema::new(SQLDialect::MariaDB);
+        add_table(&mut schema, "users", &["id", "email"]);
+
+        let table = schema.tables.get_mut("users").unwrap();
+        for name in ["email", "Email"] {
+            let key = Key::new(name.into(), true, "users".into(), vec!["email".into()]);
+            table.key_names.push(name.into());
+            table.keys.insert(name.into(), key);
+        }
+
+        let mut options = Options {
+            name_conflicts: NameConflicts::Error,
+            ..Default::default()
+        };
+
+        let mut diagnostics = Diagnostics::new(false);
+        name_members(&mut schema, &options, &mut diagnostics);
+        assert!(diagnostics.has_errors());
+
+        options
+            .method_names
+            .insert("users.Email".into(), "getByAnyEmail".into());
+
+        let mut diagnostics = Diagnostics::new(false);
+        name_members(&mut schema, &options, &mut diagnostics);
+        assert!(diagnostics.is_empty());
+
+        let table = &schema.tables["users"];
+        assert_eq!(
+            table.keys["email"].method_name.as_deref(),
+            Some("getByEmail")
+        );
+        assert_eq!(
+            table.keys["Email"].method_name.as_deref(),
+            Some("getByAnyEmail")
+        );
+    }
+
+    #[test]
+    fn suffixes_json_path_getters_like_other_getters() {
+        let mut schema = parse_source(
+            "CREATE TABLE users (id INT PRIMARY KEY, data JSON, city VARCHAR(50) AS (json_value(data, '$.city')), town VARCHAR(50) AS (json_value(data, '$.city')), KEY city (city), KEY town (town));",
+            SQLDialect::MariaDB,
+        );
+
+        let mut diagnostics = Diagnostics::new(false);
+        name_members(&mut schema, &Options::default(), &mut diagnostics);
+
+        let table = &schema.tables["users"];
+        let names = ["city", "town"].map(|name| table.keys[name].json_path_method_name.clone());
+        assert_eq!(
+            names,
+            [
+                Some("getByDataCityJsonPath".to_string()),
+                Some("getByDataCityJsonPath2".to_string())
+            ]
+        );
+        assert_eq!(
+            diagnostics.to_string(),
+            "warning: the getter `getByDataCityJsonPath` of `users.town.getByDataCityJsonPath` is already used, it is named `getByDataCityJsonPath2` instead\n\n"
+        );
+    }
+
+    #[test]
+    fn renames_methods_by_their_default_name() {
+        let mut schema = parse_source(
+            "CREATE TABLE users (id INT PRIMARY KEY, email TEXT NOT NULL);
+            CREATE TABLE roles (id INT PRIMARY KEY);
+            CREATE TABLE user_roles (user_id INT REFERENCES users (id), role_id INT REFERENCES roles (id), PRIMARY KEY (user_id, role_id));
+            CREATE TABLE categories (id INT PRIMARY KEY, parent_id INT, CONSTRAINT parent FOREIGN KEY (parent_id) REFERENCES categories (id));",
+            SQLDialect::MariaDB,
+        );
+        collect_junctions(&mut schema);
+
+        let mut options = Options::default();
+        for (path, name) in [
+            ("users.user_roles.attachRole", "grantRole"),
+            ("categories.parent.getAncestors", "getParents"),
+            ("users.email.withEmail", "changeEmail"),
+        ] {
+            options.method_names.insert(path.into(), name.into());
+        }
+
+        let mut diagnostics = Diagnostics::new(false);
+        name_members(&mut schema, &options, &mut diagnostics);
+        assert!(diagnostics.is_empty(), "{}", diagnostics);
+
+        let users = &schema.tables["users"];
+        let categories = &schema.tables["categories"];
+        assert_eq!(users.junctions[0].method_names[1], "grantRole");
+        assert_eq!(
+            categories.foreign_keys["parent"].tree_method_names,
+            ["getParents", "getDescendants"]
+        );
+        assert_eq!(
+            users.columns["email"].method_name.as_deref(),
+            Some("changeEmail")
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    database::column::{Column, ColumnType},
+    options::Options,
+};
+
+use super::{
+    column::{write_parameter_value_of, write_variable},
+    sql::write_parameter_name,
+};
+
+/// Writes the `$params` of a query, nothing when the parameters are bound one
+/// by one.
+pub(crate) fn write_query_params<'a>(columns: &'a [&'a Column], options: &'a Options) -> String {
+    write_query_params_of(&with_variables(columns), options)
+}
+
+/// Writes the `$params` of a query, with the value of every column.
+pub(crate) fn write_query_params_of<'a>(
+    values: &'a [(&'a Column, String)],
+    options: &'a Options,
+) -> String {
+    let mut buffer = String::new();
+
+    if has_binary(values) {
+        return buffer;
+    }
+
+    if values.is_empty() {
+        buffer += "\t\t$params = array();\n";
+    } else if values.len() == 1 {
+        let (column, value) = &values[0];
+
+        buffer += "\t\t$params = array(\":";
+        buffer += &write_parameter_name(&column.name);
+        buffer += "\" => ";
+        buffer += &write_parameter_value_of(column, value, options);
+        buffer += ");\n";
+    } else {
+        buffer += "\t\t$params = array(\n";
+        for (column, value) in values {
+            buffer += "\t\t\t\":";
+            buffer += &write_parameter_name(&column.name);
+            buffer += "\" => ";
+            buffer += &write_parameter_value_of(column, value, options);
+            buffer += ",\n";
+        }
+        buffer += "\t\t);\n";
+    }
+
+    buffer
+}
+
+/// Executes the prepared query. `execute()` passes every value as a string, so
+/// binary values are bound as LOBs instead.
+pub(crate) fn write_query_execute<'a>(columns: &'a [&'a Column], options: &'a Options) -> String {
+    write_query_execute_of(&with_variables(columns), options)
+}
+
+/// Executes the prepared query, with the value of every column.
+pub(crate) fn write_query_execute_of<'a>(
+    values: &'a [(&'a Column, String)],
+    options: &'a Options,
+) -> String {
+    let mut buffer = String::new();
+
+    if !has_binary(values) {
+        buffer += "\t\t$sth->execute($params);\n";
+        return buffer;
+    }
+
+    for (column, value) in values {
+        buffer += "\t\t$sth->bindValue(\":";
+        buffer += &write_parameter_name(&column.name);
+        buffer += "\", ";
+        buffer += &write_parameter_value_of(column, value, options);
+        if let ColumnType::Binary(_) = column.type_ {
+            buffer += ", PDO::PARAM_LOB";
+        }
+        buffer += ");\n";
+    }
+    buffer += "\t\t$sth->execute();\n";
+
+    buffer
+}
+
+/// Executes the prepared query with the `$params` the method built. Binary
+/// values are bound as LOBs, like `write_query_execute`.
+pub(crate) fn write_query_execute_params<'a>(columns: impl Iterator<Item = &'a Column>) -> String {
+    let mut buffer = String::new();
+
+    let binary_columns = columns
+        .filter(|column| matches!(column.type_, ColumnType::Binary(_)))
+        .collect::<Vec<_>>();
+    if binary_columns.is_empty() {
+        buffer += "\t\t$sth->execute($params);\n";
+        return buffer;
+    }
+
+    buffer += "\t\tforeach ($params as $name => $value)\n";
+    buffer += "\t\t\t$sth->bindValue($name, $value, in_array($name, array(";
+    for (index, column) in binary_columns.iter().enumerate() {
+        if index > 0 {
+            buffer += ", ";
+        }
+        buffer += "\":";
+        buffer += &write_parameter_name(&column.name);
+        buffer += "\"";
+    }
+    buffer += "), true) ? PDO::PARAM_LOB : PDO::PARAM_STR);\n";
+    buffer += "\t\t$sth->execute();\n";
+
+    buffer
+}
+
+fn with_variables<'a>(columns: &'a [&'a Column]) -> Vec<(&'a Column, String)> {
+    columns
+        .iter()
+        .map(|column| (*column, write_variable(column)))
+        .collect()
+}
+
+fn has_binary<'a>(values: &'a [(&'a Column, String)]) -> bool {
+    values
+        .iter()
+        .any(|(column, _)| matches!(column.type_, ColumnType::Binary(_)))
+}
@@ -0,0 +1,133 @@
+use convert_case::{Case, Casing};
+
+/// Writes a double quoted PHP string literal.
+pub(crate) fn write_string(value: &str) -> String {
+    let mut buffer = String::new();
+
+    buffer += "\"";
+    buffer += &escape_string(value);
+    buffer += "\"";
+
+    buffer
+}
+
+/// Escapes a value to be used inside a double quoted PHP string.
+pub(crate) fn escape_string(value: &str) -> String {
+    let mut buffer = String::new();
+
+    for c in value.chars() {
+        match c {
+            '\\' => buffer += "\\\\",
+            '"' => buffer += "\\\"",
+            '$' => buffer += "\\$",
+            '\n' => buffer += "\\n",
+            '\r' => buffer += "\\r",
+            '\t' => buffer += "\\t",
+            c => buffer.push(c),
+        }
+    }
+
+    buffer
+}
+
+/// Writes a comment inside a docblock. `*/` would end the docblock, and every
+/// line after the first starts with `prefix`.
+pub(crate) fn write_docblock_text<'a>(comment: &'a str, prefix: &'a str) -> String {
+    let mut buffer = String::new();
+
+    let comment = comment.replace("*/", "*\\/").replace("\r\n", "\n");
+    for (index, line) in comment.trim().split(['\n', '\r']).enumerate() {
+        let line = line.trim_end();
+        if index > 0 {
+            buffer += "\n";
+            buffer += match line.is_empty() {
+                true => prefix.trim_end(),
+                false => prefix,
+            };
+        }
+        buffer += line;
+    }
+
+    buffer
+}
+
+/// Writes a valid and unique enum case name for every value, in order.
+pub(crate) fn write_case_names(values: &[String]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+
+    for value in values {
+        let mut name = value
+            .replace(|c: char| c.is_ascii() && !c.is_ascii_alphanumeric(), " ")
+            .to_case(Case::UpperSnake);
+
+        if name.is_empty() {
+            name = "EMPTY".into();
+        }
+        if name.starts_with(|c: char| c.is_ascii_digit()) {
+            name.insert(0, '_');
+        }
+        // `Enum::class` is the name of the enum.
+        if name.eq_ignore_ascii_case("class") {
+            name += "_";
+        }
+
+        let mut unique_name = name.clone();
+        let mut suffix = 2;
+        while names.contains(&unique_name) {
+            unique_name = format!("{}_{}", name, suffix);
+            suffix += 1;
+        }
+
+        names.push(unique_name);
+    }
+
+    names
+}
+
+/// Writes the enum case name of one of the values.
+pub(crate) fn write_case_name<'a>(values: &'a [String], value: &'a str) -> String {
+    let index = values.iter().position(|other| other == value).unwrap();
+
+    write_case_names(values).swap_remove(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_case_name, write_case_names, write_docblock_text, write_string};
+
+    #[test]
+    fn writes_valid_unique_case_names() {
+        let values = ["in-progress", "in progress", "2fa", "class", "", "café"].map(String::from);
+
+        assert_eq!(
+            write_case_names(&values),
+            [
+                "IN_PROGRESS",
+                "IN_PROGRESS_2",
+                "_2_FA",
+                "CLASS_",
+                "EMPTY",
+                "CAFÉ"
+            ]
+        );
+        assert_eq!(write_case_name(&values, "in progress"), "IN_PROGRESS_2");
+    }
+
+    #[test]
+    fn escapes_double_quoted_strings() {
+        assert_eq!(
+            write_string("say \"hi\" to $name\\n\n"),
+            r#""say \"hi\" to \$name\\n\n""#
+        );
+    }
+
+    #[test]
+    fn folds_comments_into_docblocks() {
+        let comment = "  The total, see */ below\r\n\nin cents  \n";
+
+        assert_eq!(
+            write_docblock_text(comment, "\t * "),
+            "The total, see *\\/ below\n\t *\n\t * in cents"
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use convert_case::{Case, Casing};
+
+use crate::{
+    database::{
+        keys::{ForeignKey, Key, Reference},
+        schema::Schema,
+        table::Table,
+    },
+    options::Options,
+};
+
+use super::{
+    getters::write_getter_name,
+    inflection::pluralize,
+    naming::{write_property_name, write_table_class_name},
+    trees::is_tree,
+};
+
+/// Adds every foreign key to the table it references. A foreign key without a
+/// key on its columns gets one, like the index MariaDB creates for it, so the
+/// referenced table can get its rows.
+pub(crate) fn collect_references(schema: &mut Schema) {
+    let mut table_names = schema.tables.keys().cloned().collect::<Vec<_>>();
+    table_names.sort();
+
+    for table_name in &table_names {
+        let table = schema.tables.get_mut(table_name).unwrap();
+
+        let mut references = Vec::new();
+        for foreign_key_name in table.foreign_key_names.clone() {
+            let foreign_key = &table.foreign_keys[&foreign_key_name];
+
+            if find_key(table, &foreign_key.column_names).is_none() {
+                let key = write_foreign_key_index(table, foreign_key);
+
+                table.key_names.push(key.name.clone());
+                table.keys.insert(key.name.clone(), key);
+            }
+
+            let foreign_key = &table.foreign_keys[&foreign_key_name];
+            references.push((
+                foreign_key.foreign_table_name.clone(),
+                Reference::new(table_name.clone(), foreign_key_name),
+            ));
+        }
+
+        for (foreign_table_name, reference) in references {
+            if let Some(foreign_table) = schema.tables.get_mut(&foreign_table_name) {
+                foreign_table.references.push(reference);
+            }
+        }
+    }
+}
+
+/// The key with exactly these columns, in any order.
+pub(crate) fn find_key<'a>(table: &'a Table, column_names: &'a [String]) -> Option<&'a Key> {
+    table
+        .primary_key
+        .iter()
+        .chain(table.iter_keys())
+        .find(|key| {
+            key.column_names.len() == column_names.len()
+                && key
+                    .iter_column_names()
+                    .all(|name| column_names.contains(name))
+        })
+}
+
+fn write_foreign_key_index<'a>(table: &'a Table, foreign_key: &'a ForeignKey) -> Key {
+    // The rows are unique when a unique key only uses columns of the foreign key.
+    let unique = table
+        .primary_key
+        .iter()
+        .chain(table.iter_keys())
+        .any(|key| {
+            key.unique
+                && key
+                    .iter_column_names()
+                    .all(|name| foreign_key.column_names.contains(name))
+        });
+
+    let mut name = foreign_key.column_names.join("_");
+    if table.keys.contains_key(&name) {
+        name = foreign_key.name.clone();
+    }
+
+    Key::new(
+        name,
+        unique,
+        table.name.clone(),
+        foreign_key.column_names.clone(),
+    )
+}
+
+/// The name of the getter of a reference, `getOrders` for several rows and
+/// `getProfile` for a unique one. Tables referencing the same table more than
+/// once add the columns, like `getOrdersByBuyerId`. The rows of a tree are
+/// `getChildren`.
+pub(crate) fn write_reference_name<'a>(
+    table: &'a Table,
+    reference: &'a Reference,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
+    if let Some(name) = &reference.method_name {
+        return name.clone();
+    }
+
+    let (foreign_table, foreign_key, key) = resolve(reference, schema);
+
+    if is_tree(foreign_key, schema) {
+        return match key.unique {
+            true => "getChild".to_string(),
+            false => "getChildren".to_string(),
+        };
+    }
+
+    let mut buffer = String::new();
+
+    buffer += "get";
+    if key.unique {
+        buffer += &write_table_class_name(foreign_table);
+    } else {
+        buffer += &pluralize(&write_table_class_name(foreign_table), options);
+    }
+
+    let count = table
+        .references
+        .iter()
+        .filter(|other| other.table_name == reference.table_name)
+        .count();
+    if count > 1 {
+        buffer += "By";
+        buffer += &foreign_key.column_names.join("_").to_case(Case::Pascal);
+    }
+
+    buffer
+}
+
+pub(crate) fn write_references<'a>(
+    table: &'a Table,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
+    let mut buffer = String::new();
+
+    for reference in &table.references {
+        let (foreign_table, foreign_key, key) = resolve(reference, schema);
+
+        // The columns of this table passed for each column of the key, which
+        // can have the columns of the foreign key in another order. Foreign
+        // keys on columns this table doesn't have can't be followed.
+        let Some(columns) = key
+            .iter_column_names()
+            .map(|column_name| {
+                foreign_key
+                    .iter_column_names()
+                    .zip(foreign_key.iter_foreign_column_names())
+                    .find(|(other, _)| *other == column_name)
+                    .and_then(|(_, foreign_column_name)| table.columns.get(foreign_column_name))
+            })
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+
+        buffer += &write_comment(table, foreign_table, foreign_key, key.unique);
+
+        buffer += "\tpublic function ";
+        buffer += &write_reference_name(table, reference, schema, options);
+        if key.unique {
+            buffer += "(): null|";
+            buffer += &write_table_class_name(foreign_table);
+        } else {
+            buffer += "(): array";
+        }
+        buffer += "\n\t{\n";
+
+        let nullable_columns = columns
+            .iter()
+            .filter(|column| column.nullable)
+            .collect::<Vec<_>>();
+        if !nullable_columns.is_empty() {
+            buffer += "\t\tif (!isset(";
+            for (index, column) in nullable_columns.into_iter().enumerate() {
+                if index > 0 {
+                    buffer += ", ";
+                }
+                buffer += "$this->";
+                buffer += &write_property_name(column);
+            }
+            buffer += "))\n";
+
+            if key.unique {
+                buffer += "\t\t\treturn null;\n\n";
+            } else {
+                buffer += "\t\t\treturn array();\n\n";
+            }
+        }
+
+        buffer += "\t\treturn ";
+        buffer += &write_table_class_name(foreign_table);
+        buffer += "::";
+        buffer += &write_getter_name(key);
+        buffer += "(";
+
+        for (index, column) in columns.iter().enumerate() {
+            if index > 0 {
+                buffer += ", ";
+            }
+            buffer += "$this->";
+            buffer += &write_property_name(column);
+        }
+        buffer += ");\n";
+
+        buffer += "\t}\n";
+    }
+
+    buffer
+}
+
+/// The referencing table, its foreign key and the key to get its rows by.
+fn resolve<'a>(
+    reference: &'a Reference,
+    schema: &'a Schema,
+) -> (&'a Table, &'a ForeignKey, &'a Key) {
+    let foreign_table = &schema.tables[&reference.table_name];
+    let foreign_key = &foreign_table.foreign_keys[&reference.foreign_key_name];
+    let key = find_key(foreign_table, &foreign_key.column_names).unwrap();
+
+    (foreign_table, foreign_key, key)
+}
+
+fn write_comment<'a>(
+    table: &'a Table,
+    foreign_table: &'a Table,
+    foreign_key: &'a ForeignKey,
+    unique: bool,
+) -> String {
+    let mut buffer = String::new();
+
+    buffer += "\n\t/** Gets the `";
+    buffer += &write_table_class_name(foreign_table);
+    if unique {
+        buffer += "` referencing this `";
+    } else {
+        buffer += "`s referencing this `";
+    }
+    buffer += &write_table_class_name(table);
+    buffer += "` by the `";
+    buffer += &foreign_key.name;
+    buffer += "` key.\n";
+
+    if unique {
+        buffer += "\t * @return null|";
+        buffer += &write_table_class_name(foreign_table);
+        buffer += " The corresponding object, `null` when the row doesn't exist.\n";
+    } else {
+        buffer += "\t * @return array zero or more corresponding objects.\n";
+    }
+
+    buffer += "\t */\n";
+
+    buffer
+}
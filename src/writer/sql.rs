@@ -0,0 +1,34 @@
+use crate::database::dialect::SQLDialect;
+
+use super::php::escape_string;
+
+/// Writes a quoted identifier that can be used inside a double quoted PHP string.
+pub(crate) fn write_identifier(dialect: SQLDialect, identifier: &str) -> String {
+    escape_string(&dialect.quote(identifier))
+}
+
+/// Writes the name of a query placeholder, PDO only accepts letters, digits and
+/// underscores in them.
+pub(crate) fn write_parameter_name(column_name: &str) -> String {
+    column_name.replace(|c: char| !c.is_ascii_alphanumeric() && c != '_', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::dialect::SQLDialect;
+
+    use super::{write_identifier, write_parameter_name};
+
+    #[test]
+    fn quotes_identifiers_inside_php_strings() {
+        assert_eq!(
+            write_identifier(SQLDialect::MariaDB, "shop.order`s"),
+            "`shop`.`order``s`"
+        );
+        assert_eq!(
+            write_identifier(SQLDialect::PostgreSQL, "say \"$\""),
+            r#"\"say \"\"\$\"\"\""#
+        );
+        assert_eq!(write_parameter_name("unit price (€)"), "unit_price____");
+    }
+}
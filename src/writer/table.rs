@@ -1,9 +1,5 @@
-use convert_case::{Case, Casing};
-
-use crate::database::{
-    column::{Column, ColumnType},
-    table::Table,
-};
+use crate::database::{column::Column, schema::Schema, table::Table};
+use crate::options::Options;
 
 use super::{
     column::{write_full_comment, write_typed_variable},
@@ -11,57 +7,88 @@ use super::{
     deleter::write_deleter,
     foreign_getters::write_foreign_getters,
     getters::write_getters,
+    junctions::write_junctions,
+    naming::write_table_class_name,
+    php::write_docblock_text,
+    references::write_references,
+    trees::write_trees,
     updater::write_updater,
+    withers::{write_changes_property, write_withers},
 };
 
-pub(crate) fn write_table<'a>(table: &'a Table) -> String {
+pub(crate) fn write_table<'a>(
+    table: &'a Table,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
     let mut buffer = String::new();
 
-    buffer += &write_table_definition(&table.name);
+    let dialect = schema.dialect;
+
+    buffer += &write_table_definition(table);
+
+    if table.primary_key.is_some() {
+        buffer += &write_changes_property();
+    }
 
-    buffer += &write_constructor_comment(table.iter_columns());
-    buffer += &write_constructor(table.iter_columns());
+    buffer += &write_constructor_comment(table.iter_columns(), options);
+    buffer += &write_constructor(table.iter_columns(), options);
 
-    buffer += &write_foreign_getters(table, table.iter_foreign_keys());
+    buffer += &write_foreign_getters(table, table.iter_foreign_keys(), schema, options);
+    buffer += &write_references(table, schema, options);
+    buffer += &write_junctions(table, schema, options);
+    buffer += &write_trees(table, schema, options);
+    buffer += &write_withers(table, options);
 
-    buffer += &write_getters(table);
+    buffer += &write_getters(table, dialect, options);
 
-    buffer += &write_creater(table);
+    buffer += &write_creater(table, dialect, options);
 
     if table.primary_key.is_some() {
-        buffer += &write_updater(table);
-        buffer += &write_deleter(table);
+        buffer += &write_updater(table, dialect, options);
+        buffer += &write_deleter(table, dialect, options);
     }
 
     buffer += "}\n";
 
-    buffer += &write_enums(table);
-
     buffer
 }
 
-fn write_table_definition<'a>(table_name: &'a String) -> String {
+fn write_table_definition(table: &Table) -> String {
     let mut buffer = String::new();
 
-    buffer += "/** Database class for the `";
-    buffer += table_name;
-    buffer += "` table */\n";
+    if table.comment.trim().is_empty() {
+        buffer += "/** Database class for the `";
+        buffer += &write_docblock_text(&table.name, "");
+        buffer += "` table */\n";
+    } else if table.comment.trim().contains(['\n', '\r']) {
+        buffer += "/**\n * ";
+        buffer += &write_docblock_text(&table.comment, " * ");
+        buffer += "\n */\n";
+    } else {
+        buffer += "/** ";
+        buffer += &write_docblock_text(&table.comment, "");
+        buffer += " */\n";
+    }
 
     buffer += "class ";
-    buffer += &table_name.to_case(Case::Pascal);
+    buffer += &write_table_class_name(table);
     buffer += "\n";
     buffer += "{\n";
 
     buffer
 }
 
-fn write_constructor_comment<'a>(columns: impl Iterator<Item = &'a Column>) -> String {
+fn write_constructor_comment<'a>(
+    columns: impl Iterator<Item = &'a Column>,
+    options: &'a Options,
+) -> String {
     let mut buffer = String::new();
 
     buffer += "\t/**\n";
     for column in columns {
         buffer += "\t * @param ";
-        buffer += &write_full_comment(column);
+        buffer += &write_full_comment(column, options);
         buffer += "\n";
     }
     buffer += "\t */\n";
@@ -69,13 +96,16 @@ fn write_constructor_comment<'a>(columns: impl Iterator<Item = &'a Column>) -> S
     buffer
 }
 
-fn write_constructor<'a>(columns: impl Iterator<Item = &'a Column>) -> String {
+fn write_constructor<'a>(
+    columns: impl Iterator<Item = &'a Column>,
+    options: &'a Options,
+) -> String {
     let mut buffer = String::new();
 
     buffer += "\tprivate function __construct(\n";
     for column in columns {
         buffer += "\t\tpublic readonly ";
-        buffer += &write_typed_variable(column);
+        buffer += &write_typed_variable(column, options);
         buffer += ",\n";
     }
     buffer += "\t) {\n\t}\n";
@@ -83,40 +113,34 @@ fn write_constructor<'a>(columns: impl Iterator<Item = &'a Column>) -> String {
     buffer
 }
 
-fn write_enums<'a>(table: &'a Table) -> String {
-    let mut buffer = String::new();
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
 
-    for column in table.iter_columns() {
-        let values = match &column.type_ {
-            ColumnType::Enum(values) => values,
-            _ => continue,
-        };
+    use crate::database::table::Table;
 
-        let name = column.name.to_case(Case::Pascal);
+    use super::write_table_definition;
 
-        buffer += &write_enum(&name, values);
-    }
+    fn create_table(comment: &str) -> Table {
+        let mut table = Table::new("order_lines".into(), Vec::new(), HashMap::new());
+        table.comment = comment.into();
 
-    buffer
-}
-
-fn write_enum<'a>(name: &'a String, values: &'a Vec<String>) -> String {
-    let mut buffer = String::new();
-
-    buffer += "\nenum ";
-    buffer += &name;
-    buffer += "Enum: string\n";
-    buffer += "{\n";
-
-    for value in values {
-        buffer += "\tcase ";
-        buffer += &value.to_case(Case::UpperSnake);
-        buffer += " = \"";
-        buffer += value;
-        buffer += "\";\n";
+        table
     }
 
-    buffer += "}\n";
-
-    buffer
+    #[test]
+    fn documents_the_class_with_the_table_comment() {
+        assert_eq!(
+            write_table_definition(&create_table("")),
+            "/** Database class for the `order_lines` table */\nclass OrderLines\n{\n"
+        );
+        assert_eq!(
+            write_table_definition(&create_table("Lines of an order")),
+            "/** Lines of an order */\nclass OrderLines\n{\n"
+        );
+        assert_eq!(
+            write_table_definition(&create_table("Lines of an order\nin the shop")),
+            "/**\n * Lines of an order\n * in the shop\n */\nclass OrderLines\n{\n"
+        );
+    }
 }
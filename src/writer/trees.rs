@@ -0,0 +1,470 @@
+use crate::{
+    database::{keys::ForeignKey, schema::Schema, table::Table},
+    options::Options,
+};
+
+use super::{
+    column::write_row_value,
+    foreign_getters::write_foreign_name,
+    naming::{write_property_name, write_table_class_name, write_table_variable},
+    params::{write_query_execute_of, write_query_params_of},
+    sql::{write_identifier, write_parameter_name},
+};
+
+/// Whether a foreign key is the only one referencing its own table, like
+/// `parent_id` of `categories`. Its getters are named `getParent`,
+/// `getChildren`, `getAncestors` and `getDescendants`.
+pub(crate) fn is_tree<'a>(foreign_key: &'a ForeignKey, schema: &'a Schema) -> bool {
+    if foreign_key.foreign_table_name != foreign_key.table_name {
+        return false;
+    }
+
+    let Some(table) = schema.tables.get(&foreign_key.table_name) else {
+        return false;
+    };
+
+    table
+        .iter_foreign_keys()
+        .filter(|other| other.foreign_table_name == table.name)
+        .count()
+        == 1
+}
+
+/// The getters of the ancestors and descendants of a foreign key referencing
+/// its own table, `getAncestors` and `getDescendants`. Tables referencing
+/// themselves more than once add the name of the foreign key, like
+/// `getManagerAncestors`.
+pub(crate) fn write_tree_names<'a>(
+    foreign_key: &'a ForeignKey,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> Vec<String> {
+    if !foreign_key.tree_method_names.is_empty() {
+        return foreign_key.tree_method_names.clone();
+    }
+
+    if is_tree(foreign_key, schema) {
+        return vec!["getAncestors".to_string(), "getDescendants".to_string()];
+    }
+
+    let name = write_foreign_name(foreign_key, schema, options);
+
+    vec![
+        "get".to_string() + &name + "Ancestors",
+        "get".to_string() + &name + "Descendants",
+    ]
+}
+
+/// The foreign keys of a table referencing its own columns.
+pub(crate) fn iter_tree_foreign_keys(table: &Table) -> impl Iterator<Item = &ForeignKey> {
+    table.iter_foreign_keys().filter(|foreign_key| {
+        foreign_key.foreign_table_name == table.name
+            && foreign_key
+                .iter_column_names()
+                .chain(foreign_key.iter_foreign_column_names())
+                .all(|column_name| table.columns.contains_key(column_name))
+    })
+}
+
+pub(crate) fn write_trees<'a>(
+    table: &'a Table,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
+    let mut buffer = String::new();
+
+    for foreign_key in iter_tree_foreign_keys(table) {
+        let names = write_tree_names(foreign_key, schema, options);
+
+        buffer += &write_tree_getter(table, foreign_key, &names[0], true, schema, options);
+        buffer += &write_tree_getter(table, foreign_key, &names[1], false, schema, options);
+    }
+
+    buffer
+}
+
+/// Gets every ancestor or descendant with a single recursive query. The query
+/// keeps the path of keys it followed, so it stops on cycles, and the depth,
+/// so it can stop at a level.
+fn write_tree_getter<'a>(
+    table: &'a Table,
+    foreign_key: &'a ForeignKey,
+    name: &'a str,
+    ancestors: bool,
+    schema: &'a Schema,
+    options: &'a Options,
+) -> String {
+    let mut buffer = String::new();
+
+    let class_name = write_table_class_name(table);
+    let key_columns = foreign_key
+        .iter_foreign_column_names()
+        .map(|column_name| &table.columns[column_name])
+        .collect::<Vec<_>>();
+
+    buffer += "\n\t/** Gets the ";
+    if ancestors {
+        buffer += "ancestors of this `";
+        buffer += &class_name;
+        buffer += "`, the nearest first,";
+    } else {
+        buffer += "descendants of this `";
+        buffer += &class_name;
+        buffer += "`, level by level,";
+    }
+    buffer += " by the `";
+    buffer += &foreign_key.name;
+    buffer += "` key.\n";
+    buffer +=
+        "\t * @param null|int $maxDepth The number of levels to follow, `null` for all of them.\n";
+    buffer += "\t * @return array zero or more corresponding objects.\n";
+    buffer += "\t */\n";
+
+    buffer += "\tpublic function ";
+    buffer += name;
+    buffer += "(null|int $maxDepth = null): array\n";
+    buffer += "\t{\n";
+
+    let nullable_columns = key_columns
+        .iter()
+        .filter(|column| column.nullable)
+        .collect::<Vec<_>>();
+    if !nullable_columns.is_empty() {
+        buffer += "\t\tif (!isset(";
+        for (index, column) in nullable_columns.into_iter().enumerate() {
+            if index > 0 {
+                buffer += ", ";
+            }
+            buffer += "$this->";
+            buffer += &write_property_name(column);
+        }
+        buffer += "))\n";
+        buffer += "\t\t\treturn array();\n\n";
+    }
+
+    let values = key_columns
+        .iter()
+        .map(|column| {
+            (
+                *column,
+                "$this->".to_string() + &write_property_name(column),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    buffer += &write_query_params_of(&values, options);
+
+    buffer += "\t\t$sth = getPDO()->prepare(\"";
+    buffer += &write_query(table, foreign_key, ancestors, schema);
+    buffer += ";\");\n";
+
+    buffer += &write_query_execute_of(&values, options);
+    buffer += "\n";
+
+    let variable = write_table_variable(&table.name);
+
+    buffer += "\t\t$";
+    buffer += &variable;
+    buffer += " = array();\n\n";
+    buffer += "\t\twhile ($row = $sth->fetch())\n";
+    buffer += "\t\t\t$";
+    buffer += &variable;
+    buffer += "[] = new ";
+    buffer += &class_name;
+    buffer += "(";
+    for (index, column) in table.iter_columns().enumerate() {
+        if index > 0 {
+            buffer += ", ";
+        }
+        buffer += &write_row_value(column, options);
+    }
+    buffer += ");\n\n";
+
+    buffer += "\t\treturn $";
+    buffer += &variable;
+    buffer += ";\n";
+
+    buffer += "\t}\n";
+
+    buffer
+}
+
+/// The recursive query, starting from the row of `$this` at depth 0 and
+/// following the foreign key up or down. The depth limit is added by PHP, it
+/// can't be a parameter as PDO passes parameters as strings.
+fn write_query<'a>(
+    table: &'a Table,
+    foreign_key: &'a ForeignKey,
+    ancestors: bool,
+    schema: &'a Schema,
+) -> String {
+    let mut buffer = String::new();
+
+    let dialect = schema.dialect;
+    let key_columns = foreign_key
+        .iter_foreign_column_names()
+        .map(|column_name| &table.columns[column_name])
+        .collect::<Vec<_>>();
+
+    // The table the query walks, with the columns linking the rows.
+    let mut tree_column_names = Vec::new();
+    for column_name in foreign_key
+        .iter_foreign_column_names()
+        .chain(foreign_key.iter_column_names())
+    {
+        if !tree_column_names.contains(column_name) {
+            tree_column_names.push(column_name.clone());
+        }
+    }
+    let tree_name = write_free_name("tree", std::slice::from_ref(&table.name));
+    let depth_name = write_free_name("depth", &tree_column_names);
+    let path_name = write_free_name("path", &tree_column_names);
+
+    let qualify = |table_name: &str, column_name: &str| {
+        write_identifier(dialect, table_name) + "." + &write_identifier(dialect, column_name)
+    };
+
+    // The keys in the path are separated by commas, their columns by
+    // semicolons.
+    let write_key = |table_name: &str| {
+        let mut parts = Vec::new();
+        for (index, column) in key_columns.iter().enumerate() {
+            if index > 0 {
+                parts.push("';'".to_string());
+            }
+            parts.push(qualify(table_name, &column.name));
+        }
+        parts
+    };
+
+    buffer += "WITH RECURSIVE ";
+    buffer += &write_identifier(dialect, &tree_name);
+    buffer += " (";
+    for column_name in &tree_column_names {
+        buffer += &write_identifier(dialect, column_name);
+        buffer += ", ";
+    }
+    buffer += &write_identifier(dialect, &depth_name);
+    buffer += ", ";
+    buffer += &write_identifier(dialect, &path_name);
+    buffer += ") AS (";
+
+    buffer += "SELECT ";
+    for column_name in &tree_column_names {
+        buffer += &qualify(&table.name, column_name);
+        buffer += ", ";
+    }
+    buffer += "0, CAST(";
+    let mut path = vec!["','".to_string()];
+    path.extend(write_key(&table.name));
+    path.push("','".to_string());
+    buffer += &dialect.concat(&path);
+    buffer += " AS ";
+    buffer += dialect.string_type();
+    buffer += ") FROM ";
+    buffer += &write_identifier(dialect, &table.name);
+    buffer += " WHERE ";
+    for (index, column) in key_columns.iter().enumerate() {
+        if index > 0 {
+            buffer += " AND ";
+        }
+        buffer += &qualify(&table.name, &column.name);
+        buffer += " = :";
+        buffer += &write_parameter_name(&column.name);
+    }
+
+    buffer += " UNION ALL SELECT ";
+    for column_name in &tree_column_names {
+        buffer += &qualify(&table.name, column_name);
+        buffer += ", ";
+    }
+    buffer += &qualify(&tree_name, &depth_name);
+    buffer += " + 1, ";
+    let mut path = vec![qualify(&tree_name, &path_name)];
+    path.extend(write_key(&table.name));
+    path.push("','".to_string());
+    buffer += &dialect.concat(&path);
+    buffer += " FROM ";
+    buffer += &write_identifier(dialect, &table.name);
+    buffer += " INNER JOIN ";
+    buffer += &write_identifier(dialect, &tree_name);
+    buffer += " ON ";
+    for (index, (column_name, foreign_column_name)) in foreign_key
+        .iter_column_names()
+        .zip(foreign_key.iter_foreign_column_names())
+        .enumerate()
+    {
+        if index > 0 {
+            buffer += " AND ";
+        }
+        // Parents are referenced by the row, children reference it.
+        if ancestors {
+            buffer += &qualify(&table.name, foreign_column_name);
+            buffer += " = ";
+            buffer += &qualify(&tree_name, column_name);
+        } else {
+            buffer += &qualify(&table.name, column_name);
+            buffer += " = ";
+            buffer += &qualify(&tree_name, foreign_column_name);
+        }
+    }
+    buffer += " WHERE ";
+    buffer += &qualify(&tree_name, &path_name);
+    buffer += " NOT LIKE ";
+    let mut pattern = vec!["'%,'".to_string()];
+    pattern.extend(write_key(&table.name));
+    pattern.push("',%'".to_string());
+    buffer += &dialect.concat(&pattern);
+    buffer += "\" . ($maxDepth === null ? \"\" : \" AND ";
+    buffer += &qualify(&tree_name, &depth_name);
+    buffer += " < \" . $maxDepth) . \")";
+
+    buffer += " SELECT ";
+    buffer += &write_identifier(dialect, &table.name);
+    buffer += ".* FROM ";
+    buffer += &write_identifier(dialect, &table.name);
+    buffer += " INNER JOIN ";
+    buffer += &write_identifier(dialect, &tree_name);
+    buffer += " ON ";
+    for (index, column) in key_columns.iter().enumerate() {
+        if index > 0 {
+            buffer += " AND ";
+        }
+        buffer += &qualify(&table.name, &column.name);
+        buffer += " = ";
+        buffer += &qualify(&tree_name, &column.name);
+    }
+    buffer += " WHERE ";
+    buffer += &qualify(&tree_name, &depth_name);
+    buffer += " > 0 ORDER BY ";
+    buffer += &qualify(&tree_name, &depth_name);
+
+    buffer
+}
+
+/// A name that isn't one of the others, with underscores in front.
+fn write_free_name<'a>(name: &'a str, others: &'a [String]) -> String {
+    let mut name = name.to_string();
+    while others.contains(&name) {
+        name = "_".to_string() + &name;
+    }
+
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use crate::{
+        database::{dialect::SQLDialect, schema::Schema},
+        diagnostics::Diagnostics,
+        options::Options,
+        parsers::parse_files,
+        writer::naming::name_members,
+    };
+
+    use super::{is_tree, iter_tree_foreign_keys, write_query, write_tree_names};
+
+    /// Parses a schema file like the binary does.
+    fn parse_schema(name: &str, source: &str, dialect: SQLDialect) -> Schema {
+        let path = env::temp_dir().join(format!("database-classes-{}-{}.sql", name, process::id()));
+        fs::write(&path, source).unwrap();
+
+        let mut diagnostics = Diagnostics::new(false);
+        let schema = parse_files(&[&path], Some(dialect), &mut diagnostics);
+        fs::remove_file(&path).unwrap();
+
+        let mut schema = schema.unwrap();
+        name_members(&mut schema, &Options::default(), &mut diagnostics);
+        assert!(diagnostics.is_empty(), "{}", diagnostics);
+
+        schema
+    }
+
+    #[test]
+    fn names_the_getters_of_self_references() {
+        let schema = parse_schema(
+            "names",
+            "CREATE TABLE categories (id INT PRIMARY KEY, parent_id INT REFERENCES categories (id));
+            CREATE TABLE employees (
+                id INT PRIMARY KEY,
+                manager_id INT REFERENCES employees (id),
+                mentor_id INT REFERENCES employees (id)
+            );",
+            SQLDialect::PostgreSQL,
+        );
+        let options = Options::default();
+
+        let categories = &schema.tables["categories"];
+        let parent = iter_tree_foreign_keys(categories).next().unwrap();
+        assert!(is_tree(parent, &schema));
+        assert_eq!(
+            write_tree_names(parent, &schema, &options),
+            ["getAncestors", "getDescendants"]
+        );
+
+        let names = iter_tree_foreign_keys(&schema.tables["employees"])
+            .map(|foreign_key| {
+                assert!(!is_tree(foreign_key, &schema));
+                write_tree_names(foreign_key, &schema, &options)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                ["getManagerAncestors", "getManagerDescendants"],
+                ["getMentorAncestors", "getMentorDescendants"]
+            ]
+        );
+    }
+
+    #[test]
+    fn walks_the_tree_with_a_recursive_query() {
+        let schema = parse_schema(
+            "query",
+            "CREATE TABLE categories (id INT NOT NULL, parent_id INT, PRIMARY KEY (id), FOREIGN KEY (parent_id) REFERENCES categories (id));",
+            SQLDialect::MariaDB,
+        );
+        let table = &schema.tables["categories"];
+        let foreign_key = iter_tree_foreign_keys(table).next().unwrap();
+
+        let start = "WITH RECURSIVE `tree` (`id`, `parent_id`, `depth`, `path`) AS (\
+            SELECT `categories`.`id`, `categories`.`parent_id`, 0, CAST(CONCAT(',', `categories`.`id`, ',') AS CHAR(10000)) \
+            FROM `categories` WHERE `categories`.`id` = :id \
+            UNION ALL SELECT `categories`.`id`, `categories`.`parent_id`, `tree`.`depth` + 1, CONCAT(`tree`.`path`, `categories`.`id`, ',') \
+            FROM `categories` INNER JOIN `tree` ON ";
+        let end = " WHERE `tree`.`path` NOT LIKE CONCAT('%,', `categories`.`id`, ',%')\" \
+            . ($maxDepth === null ? \"\" : \" AND `tree`.`depth` < \" . $maxDepth) . \") \
+            SELECT `categories`.* FROM `categories` INNER JOIN `tree` ON `categories`.`id` = `tree`.`id` \
+            WHERE `tree`.`depth` > 0 ORDER BY `tree`.`depth`";
+
+        // Ancestors follow the foreign key up, descendants down.
+        assert_eq!(
+            write_query(table, foreign_key, true, &schema),
+            format!("{}`categories`.`id` = `tree`.`parent_id`{}", start, end)
+        );
+        assert_eq!(
+            write_query(table, foreign_key, false, &schema),
+            format!("{}`categories`.`parent_id` = `tree`.`id`{}", start, end)
+        );
+    }
+
+    #[test]
+    fn keeps_the_query_columns_apart_from_the_key() {
+        let schema = parse_schema(
+            "free",
+            "CREATE TABLE tree (path TEXT PRIMARY KEY, depth TEXT REFERENCES tree (path));",
+            SQLDialect::Sqlite,
+        );
+        let table = &schema.tables["tree"];
+        let foreign_key = iter_tree_foreign_keys(table).next().unwrap();
+
+        let query = write_query(table, foreign_key, true, &schema);
+
+        // Quoted for a PHP string.
+        assert!(query.starts_with(
+            r#"WITH RECURSIVE \"_tree\" (\"path\", \"depth\", \"_depth\", \"_path\") AS ("#
+        ));
+    }
+}
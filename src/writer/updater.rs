@@ -1,44 +1,85 @@
-use convert_case::{Case, Casing};
-
-use crate::database::{column::Column, table::Table};
-
-use super::column::{write_full_comment, write_typed_variable, write_variable};
-
-pub(crate) fn write_updater<'a>(table: &'a Table) -> String {
+use crate::{
+    database::{column::Column, dialect::SQLDialect, table::Table},
+    options::Options,
+};
+
+use super::{
+    column::{write_full_comment, write_parameter_value_of, write_typed_variable, write_variable},
+    getters::write_getter_name,
+    naming::{write_property_name, write_table_class_name},
+    params::{write_query_execute, write_query_execute_params, write_query_params},
+    php::write_string,
+    sql::{write_identifier, write_parameter_name},
+    withers::iter_changeable,
+};
+
+pub(crate) fn write_updater<'a>(
+    table: &'a Table,
+    dialect: SQLDialect,
+    options: &'a Options,
+) -> String {
     let mut buffer = String::new();
 
-    buffer += &write_comment(table);
+    buffer += &write_comment(table, options);
 
     buffer += "\tpublic static function update(";
 
-    buffer += &write_parameters(table.iter_columns());
+    buffer += &write_parameters(iter_updated(table), options);
 
     buffer += "): ";
-    buffer += &table.name.to_case(Case::Pascal);
+    buffer += &write_table_class_name(table);
     buffer += "\n\t{\n";
 
-    buffer += &write_body(table);
+    buffer += &write_body(table, dialect, options);
 
     buffer += "\t}\n";
 
+    buffer += &write_saver(table);
+    buffer += &write_change_saver(table, dialect, options);
+
     buffer
 }
 
-fn write_comment<'a>(table: &'a Table) -> String {
+/// Saves an object with `update()`, passing its own values.
+fn write_saver(table: &Table) -> String {
+    let mut buffer = String::new();
+
+    buffer += "\n\t/** Saves this `";
+    buffer += &write_table_class_name(table);
+    buffer += "` by the primary key.\n";
+    buffer += "\t * @return ";
+    buffer += &write_table_class_name(table);
+    buffer += " The saved object.\n";
+    buffer += "\t */\n";
+
+    buffer += "\tpublic function save(): ";
+    buffer += &write_table_class_name(table);
+    buffer += "\n\t{\n";
+
+    buffer += "\t\treturn Self::update(";
+    buffer += &write_property_arguments(iter_updated(table));
+    buffer += ");\n";
+
+    buffer += "\t}\n";
+
+    buffer
+}
+
+fn write_comment<'a>(table: &'a Table, options: &'a Options) -> String {
     let mut buffer = String::new();
 
     buffer += "\n\t/** Updates a `";
-    buffer += &table.name.to_case(Case::Pascal);
+    buffer += &write_table_class_name(table);
     buffer += "` by the primary key.\n";
 
-    for column in table.iter_columns() {
+    for column in iter_updated(table) {
         buffer += "\t * @param ";
-        buffer += &write_full_comment(column);
+        buffer += &write_full_comment(column, options);
         buffer += "\n";
     }
 
     buffer += "\t * @return ";
-    buffer += &table.name.to_case(Case::Pascal);
+    buffer += &write_table_class_name(table);
     buffer += " The updated object.\n";
 
     buffer += "\t */\n";
@@ -46,128 +87,229 @@ fn write_comment<'a>(table: &'a Table) -> String {
     buffer
 }
 
-fn write_parameters<'a>(mut columns: impl Iterator<Item = &'a Column>) -> String {
+/// Generated columns are computed by the database and can't be updated, like
+/// identity columns outside the primary key.
+fn iter_updated(table: &Table) -> impl Iterator<Item = &Column> {
+    let primary_key = table.primary_key.as_ref().unwrap();
+
+    table.iter_columns().filter(move |column| {
+        !column.generated
+            && (!column.auto_increment || primary_key.column_names.contains(&column.name))
+    })
+}
+
+fn write_parameters<'a>(
+    mut columns: impl Iterator<Item = &'a Column>,
+    options: &'a Options,
+) -> String {
     let mut buffer = String::new();
 
     if let Some(column) = columns.next() {
-        buffer += &write_typed_variable(column);
+        buffer += &write_typed_variable(column, options);
     }
 
     for column in columns {
         buffer += ", ";
-        buffer += &write_typed_variable(column);
+        buffer += &write_typed_variable(column, options);
     }
 
     buffer
 }
 
-fn write_body<'a>(table: &'a Table) -> String {
+fn write_body<'a>(table: &'a Table, dialect: SQLDialect, options: &'a Options) -> String {
     let mut buffer = String::new();
 
-    buffer += &write_query_params(table.iter_columns().collect());
+    let columns = iter_updated(table).collect::<Vec<_>>();
+
+    buffer += &write_query_params(&columns, options);
 
-    buffer += &write_query_prepare(table);
-    buffer += "\t\t$sth->execute($params);\n\n";
+    buffer += &write_query_prepare(table, dialect);
+    buffer += &write_query_execute(&columns, options);
+    buffer += "\n";
 
-    buffer += "\t\treturn Self::get(";
+    // Read the row back, so values the database computed are included.
     let primary_key = table.primary_key.as_ref().unwrap();
+    buffer += "\t\treturn Self::";
+    buffer += &write_getter_name(primary_key);
+    buffer += "(";
     buffer += &write_object_arguments(primary_key.iter_columns(table));
     buffer += ");\n";
 
     buffer
 }
 
-fn write_query_params<'a>(columns: Vec<&'a Column>) -> String {
+/// Updates only the columns the with-ers changed, so concurrent changes to
+/// the other columns are kept.
+fn write_change_saver<'a>(table: &'a Table, dialect: SQLDialect, options: &'a Options) -> String {
     let mut buffer = String::new();
 
-    if columns.len() == 1 {
-        let column = columns[0];
-
-        buffer += "\t\t$params = array(\":";
-        buffer += &column.name;
-        buffer += "\" => $";
-        buffer += &column.name.to_case(Case::Camel);
-        buffer += ");\n";
-    } else if columns.len() > 1 {
-        buffer += "\t\t$params = array(\n";
-        for column in columns {
-            buffer += "\t\t\t\":";
-            buffer += &column.name;
-            buffer += "\" => $";
-            buffer += &column.name.to_case(Case::Camel);
-            buffer += ",\n";
+    let columns = iter_changeable(table).collect::<Vec<_>>();
+    if columns.is_empty() {
+        return buffer;
+    }
+
+    let primary_key = table.primary_key.as_ref().unwrap();
+    let write_value = |column: &Column| {
+        write_parameter_value_of(
+            column,
+            &("$this->".to_string() + &write_property_name(column)),
+            options,
+        )
+    };
+
+    buffer += "\n\t/** Saves the columns of this `";
+    buffer += &write_table_class_name(table);
+    buffer += "` changed by the with-ers.\n";
+    buffer += "\t * @return ";
+    buffer += &write_table_class_name(table);
+    buffer += " The saved object.\n";
+    buffer += "\t */\n";
+
+    buffer += "\tpublic function saveChanges(): ";
+    buffer += &write_table_class_name(table);
+    buffer += "\n\t{\n";
+
+    buffer += "\t\tif (empty($this->changedColumns))\n";
+    buffer += "\t\t\treturn $this;\n\n";
+
+    buffer += "\t\t$set = array();\n";
+    buffer += "\t\t$params = array(";
+    for (index, column) in primary_key.iter_columns(table).enumerate() {
+        if index > 0 {
+            buffer += ", ";
         }
-        buffer += "\t\t);\n";
+        buffer += "\":";
+        buffer += &write_parameter_name(&column.name);
+        buffer += "\" => ";
+        buffer += &write_value(column);
+    }
+    buffer += ");\n";
+
+    for column in &columns {
+        buffer += "\t\tif (isset($this->changedColumns[";
+        buffer += &write_string(&column.name);
+        buffer += "])) {\n";
+        buffer += "\t\t\t$set[] = \"";
+        buffer += &write_identifier(dialect, &column.name);
+        buffer += " = :";
+        buffer += &write_parameter_name(&column.name);
+        buffer += "\";\n";
+        buffer += "\t\t\t$params[\":";
+        buffer += &write_parameter_name(&column.name);
+        buffer += "\"] = ";
+        buffer += &write_value(column);
+        buffer += ";\n";
+        buffer += "\t\t}\n";
     }
 
+    buffer += "\t\t$sth = getPDO()->prepare(\"UPDATE ";
+    buffer += &write_identifier(dialect, &table.name);
+    buffer += " SET \" . implode(\", \", $set) . \" WHERE ";
+    buffer += &write_query_where(primary_key.iter_columns(table), dialect);
+    buffer += ";\");\n";
+
+    buffer += &write_query_execute_params(primary_key.iter_columns(table).chain(columns));
+    buffer += "\n";
+
+    buffer += "\t\treturn Self::";
+    buffer += &write_getter_name(primary_key);
+    buffer += "(";
+    buffer += &write_property_arguments(primary_key.iter_columns(table));
+    buffer += ");\n";
+
+    buffer += "\t}\n";
+
     buffer
 }
 
-fn write_query_prepare<'a>(table: &'a Table) -> String {
+fn write_query_prepare(table: &Table, dialect: SQLDialect) -> String {
     let mut buffer = String::new();
 
-    buffer += "\t\t$sth = getPDO()->prepare(\"UPDATE `";
-    buffer += &table.name;
-    buffer += "` SET ";
+    buffer += "\t\t$sth = getPDO()->prepare(\"UPDATE ";
+    buffer += &write_identifier(dialect, &table.name);
+    buffer += " SET ";
 
     let primary_key = table.primary_key.as_ref().unwrap();
-    let non_primary_columns = table
-        .iter_columns()
-        .filter(|column| !primary_key.column_names.contains(&column.name));
-    buffer += &write_query_update(non_primary_columns);
+    let non_primary_columns =
+        iter_updated(table).filter(|column| !primary_key.column_names.contains(&column.name));
+    buffer += &write_query_update(non_primary_columns, dialect);
 
     buffer += " WHERE ";
 
-    buffer += &write_query_where(primary_key.iter_columns(table));
+    buffer += &write_query_where(primary_key.iter_columns(table), dialect);
 
     buffer += ";\");\n";
 
     buffer
 }
 
-fn write_query_update<'a>(mut columns: impl Iterator<Item = &'a Column>) -> String {
+fn write_query_update<'a>(
+    mut columns: impl Iterator<Item = &'a Column>,
+    dialect: SQLDialect,
+) -> String {
     let mut buffer = String::new();
 
     if let Some(column) = columns.next() {
-        buffer += "`";
-        buffer += &column.name;
-        buffer += "` = :";
-        buffer += &column.name;
+        buffer += &write_identifier(dialect, &column.name);
+        buffer += " = :";
+        buffer += &write_parameter_name(&column.name);
     }
 
     for column in columns {
-        buffer += ", `";
-        buffer += &column.name;
-        buffer += "` = :";
-        buffer += &column.name;
+        buffer += ", ";
+        buffer += &write_identifier(dialect, &column.name);
+        buffer += " = :";
+        buffer += &write_parameter_name(&column.name);
     }
 
     buffer
 }
 
-fn write_query_where<'a>(mut columns: impl Iterator<Item = &'a Column>) -> String {
+fn write_query_where<'a>(
+    mut columns: impl Iterator<Item = &'a Column>,
+    dialect: SQLDialect,
+) -> String {
     let mut buffer = String::new();
 
     if let Some(column) = columns.next() {
-        buffer += "`";
-        buffer += &column.name;
+        buffer += &write_identifier(dialect, &column.name);
         if column.nullable {
-            buffer += "` <=> :";
+            buffer += " ";
+            buffer += dialect.null_safe_equal();
+            buffer += " :";
         } else {
-            buffer += "` = :";
+            buffer += " = :";
         }
-        buffer += &column.name;
+        buffer += &write_parameter_name(&column.name);
     }
 
     for column in columns {
-        buffer += " AND `";
-        buffer += &column.name;
+        buffer += " AND ";
+        buffer += &write_identifier(dialect, &column.name);
         if column.nullable {
-            buffer += "` <=> :";
+            buffer += " ";
+            buffer += dialect.null_safe_equal();
+            buffer += " :";
         } else {
-            buffer += "` = :";
+            buffer += " = :";
         }
-        buffer += &column.name;
+        buffer += &write_parameter_name(&column.name);
+    }
+
+    buffer
+}
+
+fn write_property_arguments<'a>(mut columns: impl Iterator<Item = &'a Column>) -> String {
+    let mut buffer = String::new();
+
+    if let Some(column) = columns.next() {
+        buffer += "$this->";
+        buffer += &write_property_name(column);
+    }
+
+    for column in columns {
+        buffer += ", $this->";
+        buffer += &write_property_name(column);
     }
 
     buffer
@@ -187,3 +329,66 @@ fn write_object_arguments<'a>(mut columns: impl Iterator<Item = &'a Column>) ->
 
     buffer
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use crate::{
+        database::dialect::SQLDialect, diagnostics::Diagnostics, options::Options,
+        parsers::parse_files, writer::naming::name_members,
+    };
+
+    use super::write_change_saver;
+
+    #[test]
+    fn saves_only_the_changed_columns() {
+        let path = env::temp_dir().join(format!("database-classes-changes-{}.sql", process::id()));
+        fs::write(
+            &path,
+            "CREATE TABLE files (id BYTEA PRIMARY KEY, name TEXT NOT NULL, content BYTEA);",
+        )
+        .unwrap();
+
+        let mut diagnostics = Diagnostics::new(false);
+        let schema = parse_files(&[&path], Some(SQLDialect::PostgreSQL), &mut diagnostics);
+        fs::remove_file(&path).unwrap();
+
+        let mut schema = schema.unwrap();
+        let options = Options::default();
+        name_members(&mut schema, &options, &mut diagnostics);
+        assert!(diagnostics.is_empty(), "{}", diagnostics);
+
+        // Binary columns of the key and the changes are bound as large objects.
+        assert_eq!(
+            write_change_saver(&schema.tables["files"], schema.dialect, &options),
+            r#"
+	/** Saves the columns of this `File` changed by the with-ers.
+	 * @return File The saved object.
+	 */
+	public function saveChanges(): File
+	{
+		if (empty($this->changedColumns))
+			return $this;
+
+		$set = array();
+		$params = array(":id" => $this->id);
+		if (isset($this->changedColumns["name"])) {
+			$set[] = "\"name\" = :name";
+			$params[":name"] = $this->name;
+		}
+		if (isset($this->changedColumns["content"])) {
+			$set[] = "\"content\" = :content";
+			$params[":content"] = $this->content;
+		}
+		$sth = getPDO()->prepare("UPDATE \"files\" SET " . implode(", ", $set) . " WHERE \"id\" = :id;");
+		foreach ($params as $name => $value)
+			$sth->bindValue($name, $value, in_array($name, array(":id", ":content"), true) ? PDO::PARAM_LOB : PDO::PARAM_STR);
+		$sth->execute();
+
+		return Self::get($this->id);
+	}
+"#
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use convert_case::{Case, Casing};
+
+use crate::{
+    database::{column::Column, table::Table},
+    options::Options,
+};
+
+use super::{
+    column::{write_full_comment, write_typed_variable, write_variable},
+    naming::{write_property_name, write_table_class_name, write_table_variable},
+    php::write_string,
+};
+
+/// The columns with a with-er, saved by `saveChanges()`. Primary key columns
+/// find the row to update, generated and identity columns can't be written.
+pub(crate) fn iter_changeable(table: &Table) -> impl Iterator<Item = &Column> {
+    let primary_column_names = match &table.primary_key {
+        Some(primary_key) => primary_key.column_names.as_slice(),
+        None => &[],
+    };
+
+    table.iter_columns().filter(move |column| {
+        table.primary_key.is_some()
+            && !primary_column_names.contains(&column.name)
+            && !column.generated
+            && !column.auto_increment
+    })
+}
+
+/// The name of the with-er of a column, like `withEmail` for `email`.
+pub(crate) fn write_wither_name(column: &Column) -> String {
+    match &column.method_name {
+        Some(name) => name.clone(),
+        None => "with".to_string() + &write_property_name(column).to_case(Case::Pascal),
+    }
+}
+
+/// The columns the with-ers changed, keyed by name.
+pub(crate) fn write_changes_property() -> String {
+    let mut buffer = String::new();
+
+    buffer += "\t/** The columns changed by the with-ers, saved by `saveChanges()`. */\n";
+    buffer += "\tprivate array $changedColumns = array();\n\n";
+
+    buffer
+}
+
+pub(crate) fn write_withers<'a>(table: &'a Table, options: &'a Options) -> String {
+    let mut buffer = String::new();
+
+    for column in iter_changeable(table) {
+        buffer += &write_wither(table, column, options);
+    }
+
+    buffer
+}
+
+/// Copies the object with another value, the properties are read only.
+fn write_wither<'a>(table: &'a Table, column: &'a Column, options: &'a Options) -> String {
+    let mut buffer = String::new();
+
+    let variable = write_table_variable(&table.name);
+
+    buffer += "\n\t/** Copies this `";
+    buffer += &write_table_class_name(table);
+    buffer += "` with another `";
+    buffer += &write_property_name(column);
+    buffer += "`, saved by `saveChanges()`.\n";
+    buffer += "\t * @param ";
+    buffer += &write_full_comment(column, options);
+    buffer += "\n";
+    buffer += "\t * @return static The changed copy.\n";
+    buffer += "\t */\n";
+
+    buffer += "\tpublic function ";
+    buffer += &write_wither_name(column);
+    buffer += "(";
+    buffer += &write_typed_variable(column, options);
+    buffer += "): static\n";
+    buffer += "\t{\n";
+
+    buffer += "\t\t$";
+    buffer += &variable;
+    buffer += " = new static(";
+    for (index, other) in table.iter_columns().enumerate() {
+        if index > 0 {
+            buffer += ", ";
+        }
+        if other.name == column.name {
+            buffer += &write_variable(column);
+        } else {
+            buffer += "$this->";
+            buffer += &write_property_name(other);
+        }
+    }
+    buffer += ");\n";
+
+    buffer += "\t\t$";
+    buffer += &variable;
+    buffer += "->changedColumns = $this->changedColumns;\n";
+    buffer += "\t\t$";
+    buffer += &variable;
+    buffer += "->changedColumns[";
+    buffer += &write_string(&column.name);
+    buffer += "] = true;\n\n";
+
+    buffer += "\t\treturn $";
+    buffer += &variable;
+    buffer += ";\n";
+
+    buffer += "\t}\n";
+
+    buffer
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        database::{
+            column::{Column, ColumnType},
+            keys::Key,
+            table::Table,
+        },
+        options::Options,
+    };
+
+    use super::{iter_changeable, write_withers};
+
+    /// `accounts(id, email, full_name GENERATED)`.
+    fn create_table(primary_key: bool) -> Table {
+        let mut table = Table::new(
+            "accounts".into(),
+            vec!["id".into(), "email".into(), "full_name".into()],
+            HashMap::new(),
+        );
+
+        let id = Column::new("id".into(), ColumnType::Int, false, true, "".into(), None);
+        let email = Column::new(
+            "email".into(),
+            ColumnType::String,
+            true,
+            false,
+            "".into(),
+            None,
+        );
+        let mut full_name = Column::new(
+            "full_name".into(),
+            ColumnType::String,
+            false,
+            false,
+            "".into(),
+            None,
+        );
+        full_name.generated = true;
+
+        for column in [id, email, full_name] {
+            table.columns.insert(column.name.clone(), column);
+        }
+
+        if primary_key {
+            table.primary_key = Some(Key::new(
+                "".into(),
+                true,
+                "accounts".into(),
+                vec!["id".into()],
+            ));
+        }
+        table.class_name = Some("Account".into());
+
+        table
+    }
+
+    #[test]
+    fn skips_keys_and_generated_columns() {
+        let table = create_table(true);
+        let names = iter_changeable(&table)
+            .map(|column| column.name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, ["email"]);
+
+        // Without a primary key there is no row to save the changes to.
+        assert_eq!(iter_changeable(&create_table(false)).count(), 0);
+        assert_eq!(write_withers(&create_table(false), &Options::default()), "");
+    }
+
+    #[test]
+    fn copies_the_object_with_the_changed_column() {
+        assert_eq!(
+            write_withers(&create_table(true), &Options::default()),
+            "
+	/** Copies this `Account` with another `email`, saved by `saveChanges()`.
+	 * @param null|string $email
+	 * @return static The changed copy.
+	 */
+	public function withEmail(null|string $email): static
+	{
+		$accounts = new static($this->id, $email, $this->fullName);
+		$accounts->changedColumns = $this->changedColumns;
+		$accounts->changedColumns[\"email\"] = true;
+
+		return $accounts;
+	}
+"
+        );
+    }
+}